@@ -8,7 +8,7 @@ const FILE_PATH: &str = "/etc/passwd";
 #[derive(Debug, Clone, PartialOrd, PartialEq, Ord, Eq)]
 pub struct User {
     pub name: String,
-    pub uid: i32,
+    pub uid: u32,
 }
 
 impl EtcReader<User> for User {
@@ -31,7 +31,7 @@ fn parse_cut_output(output: &str) -> Result<Vec<User>, Error> {
             let fields: Vec<&str> = row.split(':').collect();
             Ok(User {
                 name: fields[0].to_string(),
-                uid: fields[1].parse::<i32>()?,
+                uid: fields[1].parse::<u32>()?,
             })
         })
         .collect()
@@ -57,7 +57,8 @@ fn parse_dscl_output(output: &str) -> Result<Vec<User>, Error> {
             let fields: Vec<&str> = row.split_whitespace().collect();
             Ok(User {
                 name: fields[..fields.len() - 1].join(" ").to_string(),
-                uid: fields.last().unwrap().parse::<i32>()?,
+                // dscl prints some ids as negative values, such as -2 for nobody
+                uid: fields.last().unwrap().parse::<i64>()? as u32,
             })
         })
         .collect()
@@ -110,6 +111,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_dscl_with_negative_uid() {
+        let output = "nobody -2";
+        let result = parse_dscl_output(output).unwrap();
+        assert_eq!(result[0].uid, 4294967294);
+    }
+
     #[test]
     fn test_parse_dscl_output_malformed_input() {
         let output = "user1\nuser2 502";
@@ -146,6 +154,14 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_parse_cut_output_large_uid() {
+        let output = "nobody:65534\nldap_user:4000001";
+        let result = parse_cut_output(output).unwrap();
+        assert_eq!(result[0].uid, 65534);
+        assert_eq!(result[1].uid, 4000001);
+    }
+
     #[test]
     fn test_parse_cut_output_invalid_uid() {
         let output = "john:not_a_number\nmary:1001";
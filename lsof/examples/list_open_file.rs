@@ -1,7 +1,7 @@
-use lsof::lsof::{lsof, FileType, OpenFile};
+use lsof::lsof::{lsof, FileType, OpenFile, Source};
 
 fn main() {
-    display(lsof(&FileType::ALL).unwrap());
+    display(lsof(&Source::COMMAND, &FileType::ALL).unwrap());
 }
 
 fn display(files: Vec<OpenFile>) {
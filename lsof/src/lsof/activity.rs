@@ -0,0 +1,359 @@
+use crate::lsof::bytes::read_u32;
+use crate::lsof::error::Error;
+use chrono::Local;
+use std::collections::HashMap;
+use std::env::consts;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const INOTIFY_EVENT_HEADER_LENGTH: usize = 16;
+const RECEIVE_BUFFER_SIZE: usize = 65536;
+const IN_MODIFY: u32 = 0x00000002;
+const IN_CLOSE_WRITE: u32 = 0x00000008;
+const IN_MOVED_FROM: u32 = 0x00000040;
+const IN_MOVED_TO: u32 = 0x00000080;
+const IN_CREATE: u32 = 0x00000100;
+const IN_DELETE: u32 = 0x00000200;
+const IN_Q_OVERFLOW: u32 = 0x00004000;
+const IN_IGNORED: u32 = 0x00008000;
+const IN_ISDIR: u32 = 0x40000000;
+const WATCH_MASK: u32 =
+    IN_CREATE | IN_MODIFY | IN_DELETE | IN_MOVED_FROM | IN_MOVED_TO | IN_CLOSE_WRITE;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum ActivityType {
+    CREATE,
+    MODIFY,
+    DELETE,
+    MOVEDFROM,
+    MOVEDTO,
+    CLOSEWRITE,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct FileActivity {
+    pub event: ActivityType, // Kernel event type
+    pub path: String,        // Full path of the file
+    pub directory: bool,     // The file is a directory
+    pub cookie: Option<u32>, // Same value on both halves of a move
+    pub created_at: i64,     // Timestamp event reception
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RawActivity {
+    pub watch: i32,
+    pub mask: u32,
+    pub cookie: u32,
+    pub name: String,
+}
+
+// Watches directories and their subdirectories with inotify, new subdirectories are watched too.
+pub struct ActivityWatcher {
+    fd: i32,
+    timeout: Duration,
+    directories: HashMap<i32, String>, // Watched directory per watch descriptor
+    overflows: u64,                    // Queue overflows, the kernel dropped events each time
+}
+
+impl fmt::Display for ActivityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ActivityType::CREATE => "create",
+            ActivityType::MODIFY => "modify",
+            ActivityType::DELETE => "delete",
+            ActivityType::MOVEDFROM => "moved_from",
+            ActivityType::MOVEDTO => "moved_to",
+            ActivityType::CLOSEWRITE => "close_write",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl ActivityWatcher {
+    pub fn new(directories: &[String], timeout: Duration) -> Result<ActivityWatcher, Error> {
+        if consts::OS != "linux" {
+            return Err(Error::Unimplemented {
+                os: consts::OS.to_string(),
+                arch: consts::ARCH.to_string(),
+            });
+        }
+        let mut watcher = ActivityWatcher {
+            fd: inotify::init()?,
+            timeout,
+            directories: HashMap::new(),
+            overflows: 0,
+        };
+        for directory in directories {
+            watcher.watch(directory.trim_end_matches('/'))?;
+        }
+        Ok(watcher)
+    }
+
+    // Waits for the next events, returns no event when the timeout elapses.
+    pub fn receive(&mut self) -> Result<Vec<FileActivity>, Error> {
+        let mut buffer = vec![0u8; RECEIVE_BUFFER_SIZE];
+        let length = inotify::receive(self.fd, &mut buffer, self.timeout)?;
+        let created_at = Local::now().timestamp_millis();
+        let mut activities: Vec<FileActivity> = vec![];
+        for raw_activity in parse_events(&buffer[..length]) {
+            if raw_activity.mask & IN_IGNORED != 0 {
+                self.directories.remove(&raw_activity.watch);
+                continue;
+            }
+            // the queue is bounded by `fs.inotify.max_queued_events`
+            if raw_activity.mask & IN_Q_OVERFLOW != 0 {
+                self.overflows += 1;
+                continue;
+            }
+            let (Some(event), Some(directory)) = (
+                activity_type(raw_activity.mask),
+                self.directories.get(&raw_activity.watch),
+            ) else {
+                continue;
+            };
+            let path = if raw_activity.name.is_empty() {
+                directory.clone()
+            } else {
+                format!("{}/{}", directory, raw_activity.name)
+            };
+            let is_directory = raw_activity.mask & IN_ISDIR != 0;
+            if is_directory && matches!(event, ActivityType::CREATE | ActivityType::MOVEDTO) {
+                // the directory may already be gone
+                let _ = self.watch(&path);
+            }
+            activities.push(FileActivity {
+                event,
+                path,
+                directory: is_directory,
+                cookie: (raw_activity.cookie != 0).then_some(raw_activity.cookie),
+                created_at,
+            });
+        }
+        Ok(activities)
+    }
+
+    pub fn overflows(&self) -> u64 {
+        self.overflows
+    }
+
+    // Subdirectories that can not be watched, such as unreadable ones, are skipped.
+    fn watch(&mut self, directory: &str) -> Result<(), Error> {
+        let watch = inotify::add_watch(self.fd, directory, WATCH_MASK)?;
+        self.directories.insert(watch, directory.to_string());
+        for entry in fs::read_dir(directory)?.filter_map(|entry| entry.ok()) {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                let path = Path::new(directory).join(entry.file_name());
+                let _ = self.watch(&path.to_string_lossy());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ActivityWatcher {
+    fn drop(&mut self) {
+        inotify::close(self.fd);
+    }
+}
+
+fn activity_type(mask: u32) -> Option<ActivityType> {
+    if mask & IN_Q_OVERFLOW != 0 {
+        None
+    } else if mask & IN_CREATE != 0 {
+        Some(ActivityType::CREATE)
+    } else if mask & IN_MODIFY != 0 {
+        Some(ActivityType::MODIFY)
+    } else if mask & IN_DELETE != 0 {
+        Some(ActivityType::DELETE)
+    } else if mask & IN_MOVED_FROM != 0 {
+        Some(ActivityType::MOVEDFROM)
+    } else if mask & IN_MOVED_TO != 0 {
+        Some(ActivityType::MOVEDTO)
+    } else if mask & IN_CLOSE_WRITE != 0 {
+        Some(ActivityType::CLOSEWRITE)
+    } else {
+        None
+    }
+}
+
+// A read returns whole `inotify_event` structures, each followed by its NUL padded name.
+pub(crate) fn parse_events(buffer: &[u8]) -> Vec<RawActivity> {
+    let mut events: Vec<RawActivity> = vec![];
+    let mut offset = 0;
+    while offset + INOTIFY_EVENT_HEADER_LENGTH <= buffer.len() {
+        let name_length = read_u32(buffer, offset + 12) as usize;
+        let end = offset + INOTIFY_EVENT_HEADER_LENGTH + name_length;
+        if end > buffer.len() {
+            break;
+        }
+        let name = &buffer[offset + INOTIFY_EVENT_HEADER_LENGTH..end];
+        let name = &name[..name.iter().position(|&c| c == 0).unwrap_or(name.len())];
+        events.push(RawActivity {
+            watch: read_u32(buffer, offset) as i32,
+            mask: read_u32(buffer, offset + 4),
+            cookie: read_u32(buffer, offset + 8),
+            name: String::from_utf8_lossy(name).to_string(),
+        });
+        offset = end;
+    }
+    events
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::ffi::CString;
+    use std::io;
+    use std::time::Duration;
+
+    pub fn init() -> io::Result<i32> {
+        let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(fd)
+        }
+    }
+
+    pub fn add_watch(fd: i32, path: &str, mask: u32) -> io::Result<i32> {
+        let path = CString::new(path).map_err(io::Error::other)?;
+        let watch = unsafe { libc::inotify_add_watch(fd, path.as_ptr(), mask) };
+        if watch < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(watch)
+        }
+    }
+
+    pub fn receive(fd: i32, buffer: &mut [u8], timeout: Duration) -> io::Result<usize> {
+        let mut poll_fd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) };
+        if ready == 0 {
+            return Ok(0);
+        } else if ready < 0 {
+            let error = io::Error::last_os_error();
+            return match error.raw_os_error() {
+                Some(libc::EINTR) => Ok(0),
+                _ => Err(error),
+            };
+        }
+        let length =
+            unsafe { libc::read(fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len()) };
+        if length < 0 {
+            let error = io::Error::last_os_error();
+            match error.raw_os_error() {
+                Some(libc::EAGAIN) | Some(libc::EINTR) => Ok(0),
+                _ => Err(error),
+            }
+        } else {
+            Ok(length as usize)
+        }
+    }
+
+    pub fn close(fd: i32) {
+        unsafe {
+            libc::close(fd);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod inotify {
+    use std::io;
+    use std::time::Duration;
+
+    pub fn init() -> io::Result<i32> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn add_watch(_fd: i32, _path: &str, _mask: u32) -> io::Result<i32> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn receive(_fd: i32, _buffer: &mut [u8], _timeout: Duration) -> io::Result<usize> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn close(_fd: i32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event(watch: i32, mask: u32, cookie: u32, name: &str) -> Vec<u8> {
+        let padded_length = if name.is_empty() {
+            0
+        } else {
+            (name.len() + 1).div_ceil(16) * 16
+        };
+        let mut event: Vec<u8> = vec![];
+        event.extend(watch.to_ne_bytes());
+        event.extend(mask.to_ne_bytes());
+        event.extend(cookie.to_ne_bytes());
+        event.extend((padded_length as u32).to_ne_bytes());
+        event.extend(name.as_bytes());
+        event.extend(vec![0u8; padded_length - name.len()]);
+        event
+    }
+
+    #[test]
+    fn test_parse_events() {
+        let mut buffer = create_event(1, IN_CREATE, 0, "app.log");
+        buffer.extend(create_event(2, IN_MOVED_TO | IN_ISDIR, 42, "archive"));
+        buffer.extend(create_event(1, IN_IGNORED, 0, ""));
+        let events = parse_events(&buffer);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].name, "app.log");
+        assert_eq!(events[1].watch, 2);
+        assert_eq!(events[1].cookie, 42);
+        assert_eq!(events[1].name, "archive");
+        assert_eq!(events[2].mask, IN_IGNORED);
+        assert_eq!(parse_events(&buffer[..32]).len(), 1);
+        assert!(parse_events(&buffer[..20]).is_empty());
+    }
+
+    #[test]
+    fn test_activity_type() {
+        assert_eq!(
+            activity_type(IN_CREATE | IN_ISDIR),
+            Some(ActivityType::CREATE)
+        );
+        assert_eq!(
+            activity_type(IN_CLOSE_WRITE),
+            Some(ActivityType::CLOSEWRITE)
+        );
+        assert_eq!(activity_type(IN_Q_OVERFLOW), None);
+        assert_eq!(activity_type(IN_IGNORED), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_receive() {
+        use std::io::Write;
+
+        let directory = std::env::temp_dir().join(format!("lsof-activity-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let directory = directory.to_string_lossy().to_string();
+        let mut watcher =
+            ActivityWatcher::new(std::slice::from_ref(&directory), Duration::from_millis(100))
+                .unwrap();
+        fs::create_dir(format!("{directory}/nested")).unwrap();
+        watcher.receive().unwrap();
+        let path = format!("{directory}/nested/app.log");
+        fs::File::create(&path).unwrap().write_all(b"x").unwrap();
+        let activities = watcher.receive().unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(activities
+            .iter()
+            .any(|activity| activity.path == path && activity.event == ActivityType::CREATE));
+        assert!(activities
+            .iter()
+            .any(|activity| activity.path == path && activity.event == ActivityType::CLOSEWRITE));
+    }
+}
@@ -10,6 +10,10 @@ pub enum Error {
     ParseInt(#[from] num::ParseIntError),
     #[error("Error parsing float: {0}")]
     ParseFloat(#[from] num::ParseFloatError),
+    #[error("Error parsing file content: {content:}")]
+    ParseFile { content: String },
     #[error("IO error: {0}")]
     IO(#[from] io::Error),
+    #[error("Invalid glob pattern: {0}")]
+    Glob(#[from] glob::PatternError),
 }
@@ -0,0 +1,308 @@
+use crate::lsof::bytes::{read_u16, read_u32};
+use crate::lsof::error::Error;
+use std::collections::HashMap;
+
+const NLMSG_HEADER_LENGTH: usize = 16;
+const NLMSG_DONE: u16 = 3;
+const NLMSG_ERROR: u16 = 2;
+const UNIX_DIAG_MSG_LENGTH: usize = 16;
+const RTATTR_HEADER_LENGTH: usize = 4;
+const UNIX_DIAG_PEER: u16 = 2;
+const O_ACCMODE: u32 = 0o3;
+
+// Peer inode of every connected unix socket, from the sock_diag netlink interface (Linux only).
+pub(crate) fn unix_peers() -> Result<HashMap<u64, u64>, Error> {
+    let socket = sock_diag::open()?;
+    let result = sock_diag::dump_unix(socket);
+    sock_diag::close(socket);
+    result
+}
+
+// Returns the peers found in a datagram and whether the dump is complete.
+pub(crate) fn parse_unix_diag(buffer: &[u8]) -> Result<(HashMap<u64, u64>, bool), Error> {
+    let mut peers: HashMap<u64, u64> = HashMap::new();
+    let mut offset = 0;
+    while offset + NLMSG_HEADER_LENGTH <= buffer.len() {
+        let length = read_u32(buffer, offset) as usize;
+        if length < NLMSG_HEADER_LENGTH || offset + length > buffer.len() {
+            break;
+        }
+        match read_u16(buffer, offset + 4) {
+            NLMSG_DONE => return Ok((peers, true)),
+            NLMSG_ERROR => {
+                return Err(Error::ParseFile {
+                    content: "sock_diag netlink error".to_string(),
+                })
+            }
+            _ => {
+                let message = &buffer[offset + NLMSG_HEADER_LENGTH..offset + length];
+                if message.len() >= UNIX_DIAG_MSG_LENGTH {
+                    let inode = read_u32(message, 4) as u64;
+                    if let Some(peer) = find_peer(&message[UNIX_DIAG_MSG_LENGTH..]) {
+                        peers.insert(inode, peer);
+                    }
+                }
+            }
+        }
+        offset += (length + 3) & !3;
+    }
+    Ok((peers, false))
+}
+
+fn find_peer(attributes: &[u8]) -> Option<u64> {
+    let mut offset = 0;
+    while offset + RTATTR_HEADER_LENGTH <= attributes.len() {
+        let length = read_u16(attributes, offset) as usize;
+        if length < RTATTR_HEADER_LENGTH || offset + length > attributes.len() {
+            break;
+        }
+        if read_u16(attributes, offset + 2) == UNIX_DIAG_PEER && length >= 8 {
+            return Some(read_u32(attributes, offset + RTATTR_HEADER_LENGTH) as u64);
+        }
+        offset += (length + 3) & !3;
+    }
+    None
+}
+
+// Access mode of a descriptor from its `/proc/<pid>/fdinfo/<fd>` octal flags, named as lsof does
+pub(crate) fn parse_access(fdinfo: &str) -> Option<String> {
+    let flags = fdinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("flags:"))
+        .and_then(|flags| u32::from_str_radix(flags.trim(), 8).ok())?;
+    match flags & O_ACCMODE {
+        0 => Some("r".to_string()),
+        1 => Some("w".to_string()),
+        _ => Some("u".to_string()),
+    }
+}
+
+// Splits an lsof `+E` unix socket name, such as `/run/docker.sock type=STREAM ->INO=926 127,x,7u`,
+// into the bound path and the peer inode.
+pub(crate) fn parse_unix_name(name: &str) -> (String, Option<String>) {
+    let path = name.split("type=").next().unwrap_or_default().trim();
+    let peer = name
+        .split_once("->INO=")
+        .and_then(|(_, peer)| peer.split_whitespace().next())
+        .map(|peer| peer.to_string());
+    (path.to_string(), peer)
+}
+
+// Removes the `pid,command,fd` endpoints lsof `+E` appends to a pipe name, such as `pipe 42,cat,0r`.
+pub(crate) fn strip_endpoints(name: &str) -> &str {
+    let mut name = name;
+    while let Some((head, endpoint)) = name.rsplit_once(' ') {
+        let fields: Vec<&str> = endpoint.split(',').collect();
+        let is_endpoint = fields.len() >= 3
+            && fields[0].parse::<u32>().is_ok()
+            && fields[fields.len() - 1].starts_with(|c: char| c.is_ascii_digit());
+        if !is_endpoint {
+            break;
+        }
+        name = head.trim_end();
+    }
+    name
+}
+
+#[cfg(target_os = "linux")]
+mod sock_diag {
+    use crate::lsof::error::Error;
+    use std::collections::HashMap;
+    use std::io;
+    use std::mem;
+
+    const SOCK_DIAG_BY_FAMILY: u16 = 20;
+    const UDIAG_SHOW_PEER: u32 = 0x4;
+    const RECEIVE_BUFFER_SIZE: usize = 32768;
+    const TIMEOUT_SECONDS: libc::time_t = 1;
+
+    pub fn open() -> io::Result<i32> {
+        unsafe {
+            let socket = libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_SOCK_DIAG,
+            );
+            if socket < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let timeval = libc::timeval {
+                tv_sec: TIMEOUT_SECONDS,
+                tv_usec: 0,
+            };
+            if libc::setsockopt(
+                socket,
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeval as *const libc::timeval as *const libc::c_void,
+                mem::size_of::<libc::timeval>() as libc::socklen_t,
+            ) < 0
+            {
+                let error = io::Error::last_os_error();
+                libc::close(socket);
+                return Err(error);
+            }
+            Ok(socket)
+        }
+    }
+
+    pub fn dump_unix(socket: i32) -> Result<HashMap<u64, u64>, Error> {
+        let message = request_message();
+        if unsafe {
+            libc::send(
+                socket,
+                message.as_ptr() as *const libc::c_void,
+                message.len(),
+                0,
+            )
+        } < 0
+        {
+            return Err(io::Error::last_os_error().into());
+        }
+        let mut peers: HashMap<u64, u64> = HashMap::new();
+        let mut buffer = vec![0u8; RECEIVE_BUFFER_SIZE];
+        loop {
+            let length = unsafe {
+                libc::recv(
+                    socket,
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    0,
+                )
+            };
+            if length < 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            let (batch, done) = super::parse_unix_diag(&buffer[..length as usize])?;
+            peers.extend(batch);
+            if done || length == 0 {
+                return Ok(peers);
+            }
+        }
+    }
+
+    pub fn close(socket: i32) {
+        unsafe {
+            libc::close(socket);
+        }
+    }
+
+    fn request_message() -> Vec<u8> {
+        let length = super::NLMSG_HEADER_LENGTH + 24;
+        let mut message: Vec<u8> = Vec::with_capacity(length);
+        // nlmsghdr
+        message.extend((length as u32).to_ne_bytes());
+        message.extend(SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        message.extend(((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+        message.extend(1u32.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes());
+        // unix_diag_req, every state
+        message.push(libc::AF_UNIX as u8);
+        message.push(0);
+        message.extend(0u16.to_ne_bytes());
+        message.extend(u32::MAX.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes());
+        message.extend(UDIAG_SHOW_PEER.to_ne_bytes());
+        message.extend([0u8; 8]);
+        message
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sock_diag {
+    use crate::lsof::error::Error;
+    use std::collections::HashMap;
+    use std::io;
+
+    pub fn open() -> io::Result<i32> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn dump_unix(_socket: i32) -> Result<HashMap<u64, u64>, Error> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn close(_socket: i32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_diag_message(inode: u32, peer: Option<u32>) -> Vec<u8> {
+        let mut payload: Vec<u8> = vec![1, 1, 1, 0];
+        payload.extend(inode.to_ne_bytes());
+        payload.extend([0u8; 8]);
+        if let Some(peer) = peer {
+            payload.extend(8u16.to_ne_bytes());
+            payload.extend(UNIX_DIAG_PEER.to_ne_bytes());
+            payload.extend(peer.to_ne_bytes());
+        }
+        let mut message: Vec<u8> = vec![];
+        message.extend(((NLMSG_HEADER_LENGTH + payload.len()) as u32).to_ne_bytes());
+        message.extend(20u16.to_ne_bytes());
+        message.extend([0u8; 10]);
+        message.extend(payload);
+        message
+    }
+
+    #[test]
+    fn test_parse_unix_diag() {
+        let mut buffer = create_diag_message(925, Some(926));
+        buffer.extend(create_diag_message(16413, None));
+        let (peers, done) = parse_unix_diag(&buffer).unwrap();
+        assert_eq!(peers, HashMap::from([(925, 926)]));
+        assert!(!done);
+
+        let mut buffer = (NLMSG_HEADER_LENGTH as u32).to_ne_bytes().to_vec();
+        buffer.extend(NLMSG_DONE.to_ne_bytes());
+        buffer.extend([0u8; 10]);
+        assert!(parse_unix_diag(&buffer).unwrap().1);
+    }
+
+    #[test]
+    fn test_parse_access() {
+        assert_eq!(
+            parse_access("pos:\t0\nflags:\t02000000\nmnt_id:\t15\n"),
+            Some("r".to_string())
+        );
+        assert_eq!(parse_access("flags:\t01\n"), Some("w".to_string()));
+        assert_eq!(parse_access("flags:\t02004002\n"), Some("u".to_string()));
+        assert_eq!(parse_access("pos:\t0\n"), None);
+    }
+
+    #[test]
+    fn test_parse_unix_name() {
+        assert_eq!(
+            parse_unix_name("/run/docker.sock type=STREAM ->INO=926 127,dockerd,7u"),
+            ("/run/docker.sock".to_string(), Some("926".to_string()))
+        );
+        assert_eq!(parse_unix_name("type=STREAM"), ("".to_string(), None));
+    }
+
+    #[test]
+    fn test_strip_endpoints() {
+        assert_eq!(strip_endpoints("pipe 22856,sleep,1w 22857,cat,0r"), "pipe");
+        assert_eq!(strip_endpoints("/tmp/my fifo 42,cat,3r"), "/tmp/my fifo");
+        assert_eq!(strip_endpoints("pipe"), "pipe");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_unix_peers() {
+        use std::fs;
+        use std::os::fd::AsRawFd;
+        use std::os::unix::fs::MetadataExt;
+        use std::os::unix::net::UnixStream;
+
+        let (left, right) = UnixStream::pair().unwrap();
+        let inode = |stream: &UnixStream| {
+            fs::metadata(format!("/proc/self/fd/{}", stream.as_raw_fd()))
+                .unwrap()
+                .ino()
+        };
+        let peers = unix_peers().unwrap();
+        assert_eq!(peers.get(&inode(&left)), Some(&inode(&right)));
+        assert_eq!(peers.get(&inode(&right)), Some(&inode(&left)));
+    }
+}
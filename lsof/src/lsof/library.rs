@@ -0,0 +1,143 @@
+use crate::lsof::error::Error;
+use crate::lsof::proc::{pids, PROC_PATH};
+use chrono::Local;
+use std::collections::HashSet;
+use std::env::consts;
+use std::fs;
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Library {
+    pub pid: u32,        // Process ID
+    pub command: String, // Command
+    pub path: String,    // Mapped file path, without the deleted suffix
+    pub device: String,  // Device as major:minor
+    pub inode: u64,      // Inode of the mapped file
+    pub deleted: bool,   // File removed or replaced on disk since it was mapped
+    pub created_at: i64, // Timestamp collection
+}
+
+// Executable file mappings of every process, the process executable excluded.
+pub fn libraries() -> Result<Vec<Library>, Error> {
+    if consts::OS != "linux" {
+        return Err(Error::Unimplemented {
+            os: consts::OS.to_string(),
+            arch: consts::ARCH.to_string(),
+        });
+    }
+    let created_at = Local::now().timestamp_millis();
+    let mut libraries: Vec<Library> = vec![];
+    for pid in pids()? {
+        match read_libraries(pid, created_at) {
+            Ok(process_libraries) => libraries.extend(process_libraries),
+            // the process exited or is not readable
+            Err(Error::IO(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(libraries)
+}
+
+fn read_libraries(pid: u32, created_at: i64) -> Result<Vec<Library>, Error> {
+    let command = fs::read_to_string(format!("{PROC_PATH}/{pid}/comm"))?;
+    let maps = fs::read_to_string(format!("{PROC_PATH}/{pid}/maps"))?;
+    let executable = fs::read_link(format!("{PROC_PATH}/{pid}/exe"))
+        .map(|path| path.to_string_lossy().to_string())
+        .ok();
+    Ok(parse_maps(&maps, executable.as_deref())
+        .into_iter()
+        .map(|(path, device, inode, deleted)| Library {
+            pid,
+            command: command.trim_end().to_string(),
+            path,
+            device,
+            inode,
+            deleted,
+            created_at,
+        })
+        .collect())
+}
+
+fn parse_maps(maps: &str, executable: Option<&str>) -> Vec<(String, String, u64, bool)> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut libraries: Vec<(String, String, u64, bool)> = vec![];
+    for line in maps.lines() {
+        // address perms offset dev inode pathname
+        let fields: Vec<&str> = line.splitn(6, char::is_whitespace).collect();
+        if fields.len() < 6 {
+            continue;
+        }
+        let Ok(inode) = fields[4].parse::<u64>() else {
+            continue;
+        };
+        let (perms, device) = (fields[1], fields[3]);
+        let path = fields[5].trim_start();
+        if inode == 0
+            || !perms.contains('x')
+            || !path.starts_with('/')
+            || path.starts_with("/memfd:")
+            || executable == Some(path)
+            || !seen.insert(path)
+        {
+            continue;
+        }
+        let (path, deleted) = match path.strip_suffix(DELETED_SUFFIX) {
+            Some(path) => (path, true),
+            None => (path, false),
+        };
+        libraries.push((path.to_string(), device.to_string(), inode, deleted));
+    }
+    libraries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_maps() {
+        let maps = "55d0c0a00000-55d0c0a28000 r-xp 00000000 fe:00 1835023                    /usr/bin/bash
+7f2a4c000000-7f2a4c028000 r--p 00000000 fe:00 395379                     /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2a4c028000-7f2a4c1bd000 r-xp 00028000 fe:00 395379                     /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2a4c1bd000-7f2a4c215000 r--p 001bd000 fe:00 395379                     /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2a4c400000-7f2a4c4a0000 r-xp 00000000 fe:00 396120                     /usr/lib/x86_64-linux-gnu/libssl.so.3 (deleted)
+7f2a4c500000-7f2a4c600000 r--p 00000000 fe:00 400001                     /usr/lib/locale/locale-archive
+7f2a4c200000-7f2a4c221000 rw-p 00000000 00:00 0                          [heap]
+7f2a4c300000-7f2a4c301000 r-xs 00000000 00:01 2048                       /memfd:jit (deleted)
+7ffd1c1f0000-7ffd1c211000 rw-p 00000000 00:00 0                          [stack]
+";
+        assert_eq!(
+            parse_maps(maps, Some("/usr/bin/bash")),
+            vec![
+                (
+                    "/usr/lib/x86_64-linux-gnu/libc.so.6".to_string(),
+                    "fe:00".to_string(),
+                    395379,
+                    false
+                ),
+                (
+                    "/usr/lib/x86_64-linux-gnu/libssl.so.3".to_string(),
+                    "fe:00".to_string(),
+                    396120,
+                    true
+                ),
+            ]
+        );
+        assert_eq!(parse_maps(maps, None).len(), 3);
+        assert!(parse_maps("7f2a4c000000-7f2a4c028000 r-xp 0 fe:00 x /lib.so", None).is_empty());
+    }
+
+    #[test]
+    fn test_libraries() {
+        if consts::OS == "linux" {
+            let pid = std::process::id();
+            let libraries = libraries().unwrap();
+            assert!(libraries
+                .iter()
+                .any(|library| library.pid == pid && library.path.contains(".so")));
+        } else {
+            assert!(libraries().is_err());
+        }
+    }
+}
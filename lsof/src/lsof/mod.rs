@@ -1,31 +1,70 @@
 use crate::lsof::error::Error;
+use crate::lsof::proc::Proc;
+use crate::lsof::scope::Scope;
 use crate::lsof::unix::Unix;
 use chrono::Local;
 use std::env::consts;
 use std::fmt;
 
+pub mod activity;
+mod bytes;
 pub mod error;
+mod ipc;
+pub mod library;
+pub mod proc;
+pub mod scope;
 pub mod unix;
 
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum Source {
+    COMMAND, // `lsof` command output
+    PROC,    // Linux /proc filesystem
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub enum FileType {
     REGULAR,
     NETWORK,
+    IPC,          // Unix sockets, pipes and FIFOs
+    SCOPE(Scope), // Regular files of a watch scope
     ALL,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub struct OpenFile {
-    pub command: String, // Command
-    pub pid: u32,        // Process ID
-    pub uid: i16,        // User ID
-    pub fd: String,      // File Descriptor
-    pub _type: String,   // Column type
-    pub device: String,  // Device
-    pub size: u64,       // Size
-    pub node: String,    // Node
-    pub name: String,    // Name
-    pub created_at: i64, // Timestamp command execution
+    pub command: String,                        // Command
+    pub pid: u32,                               // Process ID
+    pub uid: u32,                               // User ID
+    pub fd: String,                             // File Descriptor
+    pub _type: String,                          // Column type
+    pub device: String,                         // Device
+    pub size: u64,                              // Size
+    pub node: String,                           // Node
+    pub name: String,                           // Name
+    pub ip_source_address: Option<String>,      // Local address of an internet socket
+    pub ip_source_port: Option<String>,         // Local port of an internet socket
+    pub ip_destination_address: Option<String>, // Remote address of an internet socket
+    pub ip_destination_port: Option<String>,    // Remote port of an internet socket
+    pub access: Option<String>,                 // Access mode, r, w or u
+    pub lock: Option<String>,                   // Lock status
+    pub offset: Option<u64>,                    // File offset
+    pub protocol: Option<String>,               // Socket protocol name
+    pub tcp_state: Option<String>,              // TCP connection state
+    pub recv_queue: Option<u64>,                // Receive queue length
+    pub send_queue: Option<u64>,                // Send queue length
+    pub peer_node: Option<String>,              // Node of the other end of a unix socket
+    pub link_count: Option<u64>,                // Link count, 0 once removed from disk
+    pub created_at: i64,                        // Timestamp command execution
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Source::COMMAND => "command",
+            Source::PROC => "proc",
+        };
+        write!(f, "{}", s)
+    }
 }
 
 impl fmt::Display for FileType {
@@ -33,6 +72,8 @@ impl fmt::Display for FileType {
         let s = match self {
             FileType::REGULAR => "regular",
             FileType::NETWORK => "network",
+            FileType::IPC => "ipc",
+            FileType::SCOPE(scope) => return write!(f, "scope {}", scope.path),
             FileType::ALL => "all",
         };
         write!(f, "{}", s)
@@ -40,7 +81,7 @@ impl fmt::Display for FileType {
 }
 
 impl OpenFile {
-    pub fn new(pid: u32, uid: i16, command: &str) -> Self {
+    pub fn new(pid: u32, uid: u32, command: &str) -> Self {
         OpenFile {
             command: command.to_string(),
             pid,
@@ -51,6 +92,19 @@ impl OpenFile {
             size: 0,
             node: "".to_string(),
             name: "".to_string(),
+            ip_source_address: None,
+            ip_source_port: None,
+            ip_destination_address: None,
+            ip_destination_port: None,
+            access: None,
+            lock: None,
+            offset: None,
+            protocol: None,
+            tcp_state: None,
+            recv_queue: None,
+            send_queue: None,
+            peer_node: None,
+            link_count: None,
             created_at: Local::now().timestamp_millis(),
         }
     }
@@ -60,30 +114,107 @@ pub trait Lsof {
     fn exec(file_type: &FileType) -> Result<Vec<OpenFile>, Error>;
 }
 
-pub fn lsof(file_type: &FileType) -> Result<Vec<OpenFile>, Error> {
-    if ["linux", "macos"].contains(&consts::OS) {
-        Unix::exec(file_type)
-    } else {
-        Err(Error::Unimplemented {
+pub fn lsof(source: &Source, file_type: &FileType) -> Result<Vec<OpenFile>, Error> {
+    match source {
+        Source::COMMAND if ["linux", "macos"].contains(&consts::OS) => Unix::exec(file_type),
+        Source::PROC if consts::OS == "linux" => Proc::exec(file_type),
+        _ => Err(Error::Unimplemented {
             os: consts::OS.to_string(),
             arch: consts::ARCH.to_string(),
-        })
+        }),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::lsof::{lsof, FileType};
+    use crate::lsof::{lsof, FileType, Source};
 
     #[test]
     fn test_lsof() {
-        let regular = lsof(&FileType::REGULAR).unwrap();
-        let network = lsof(&FileType::NETWORK).unwrap();
-        let all = lsof(&FileType::ALL).unwrap();
+        let regular = lsof(&Source::COMMAND, &FileType::REGULAR).unwrap();
+        let network = lsof(&Source::COMMAND, &FileType::NETWORK).unwrap();
+        let all = lsof(&Source::COMMAND, &FileType::ALL).unwrap();
         assert!(!regular.is_empty());
         assert!(!network.is_empty());
         assert!(!all.is_empty());
         assert!(regular.len() > network.len());
         assert!(all.len() > regular.len());
     }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_lsof_ipc() {
+        use std::os::unix::net::UnixStream;
+
+        let _pair = UnixStream::pair().unwrap();
+        let pid = std::process::id();
+        for source in [Source::COMMAND, Source::PROC] {
+            let ipc = lsof(&source, &FileType::IPC).unwrap();
+            assert!(ipc
+                .iter()
+                .any(|open_file| open_file.pid == pid && open_file._type == "unix"));
+            assert!(ipc
+                .iter()
+                .all(|open_file| ["unix", "FIFO", "PIPE"].contains(&open_file._type.as_str())));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_lsof_scope() {
+        use crate::lsof::scope::Scope;
+        use std::fs::File;
+
+        let directory = std::fs::canonicalize(format!("{}/src", env!("CARGO_MANIFEST_DIR")))
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let path = format!("{directory}/lib.rs");
+        let _file = File::open(&path).unwrap();
+        let pid = std::process::id();
+        let scope = Scope::new(&directory, &["*.rs".to_string()], &[], false).unwrap();
+        for source in [Source::COMMAND, Source::PROC] {
+            let open_files = lsof(&source, &FileType::SCOPE(scope.clone())).unwrap();
+            assert!(open_files
+                .iter()
+                .any(|open_file| open_file.pid == pid && open_file.name == path));
+            assert!(open_files
+                .iter()
+                .all(|open_file| open_file.name.starts_with(&directory)));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_lsof_deleted() {
+        use crate::lsof::scope::Scope;
+        use std::fs::{self, File};
+        use std::io::Write;
+
+        let directory = fs::canonicalize(std::env::temp_dir())
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let path = format!("{directory}/lsof-deleted-{}", std::process::id());
+        let mut file = File::create(&path).unwrap();
+        file.write_all(&[0u8; 4096]).unwrap();
+        fs::remove_file(&path).unwrap();
+        let pid = std::process::id();
+        let scope = Scope::new(&directory, &[], &[], true).unwrap();
+        for source in [Source::COMMAND, Source::PROC] {
+            let deleted = lsof(&source, &FileType::SCOPE(scope.clone())).unwrap();
+            let open_file = deleted
+                .iter()
+                .find(|open_file| open_file.pid == pid && open_file.name.starts_with(&path))
+                .unwrap();
+            assert_eq!(open_file.size, 4096);
+            assert_eq!(open_file.link_count, Some(0));
+        }
+    }
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(Source::COMMAND.to_string(), "command");
+        assert_eq!(Source::PROC.to_string(), "proc");
+    }
 }
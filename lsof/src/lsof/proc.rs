@@ -0,0 +1,696 @@
+use crate::lsof::error::Error;
+use crate::lsof::ipc::{parse_access, unix_peers};
+use crate::lsof::{FileType, Lsof, OpenFile};
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::Metadata;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+pub(crate) const PROC_PATH: &str = "/proc";
+pub(crate) const DELETED_SUFFIX: &str = " (deleted)";
+pub(crate) const MEMFD_PREFIX: &str = "/memfd:";
+const INTERNET_SOCKET_TABLES: [(&str, &str, &str); 4] = [
+    ("tcp", "IPv4", "TCP"),
+    ("udp", "IPv4", "UDP"),
+    ("tcp6", "IPv6", "TCP"),
+    ("udp6", "IPv6", "UDP"),
+];
+// Kernel TCP states, indexed by their code in `/proc/net/tcp`, named as lsof does
+const TCP_STATES: [&str; 12] = [
+    "ESTABLISHED",
+    "SYN_SENT",
+    "SYN_RECV",
+    "FIN_WAIT1",
+    "FIN_WAIT2",
+    "TIME_WAIT",
+    "CLOSE",
+    "CLOSE_WAIT",
+    "LAST_ACK",
+    "LISTEN",
+    "CLOSING",
+    "NEW_SYN_RECV",
+];
+
+pub struct Proc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Socket {
+    pub _type: String,             // IPv4 or IPv6
+    pub protocol: String,          // TCP or UDP
+    pub local: (IpAddr, u16),      // Local address and port
+    pub remote: (IpAddr, u16),     // Remote address and port
+    pub tcp_state: Option<String>, // Connection state, TCP only
+    pub send_queue: u64,           // Send queue length
+    pub recv_queue: u64,           // Receive queue length
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Context {
+    pub file_type: FileType,
+    pub sockets: HashMap<u64, Socket>, // Internet sockets per inode
+    pub unix_sockets: HashMap<u64, String>, // Unix socket paths per inode, empty if unbound
+    pub unix_peers: HashMap<u64, u64>, // Peer inode per connected unix socket inode
+    pub root_device: u64,              // Device of the root filesystem, or of the scope path
+    pub created_at: i64,
+}
+
+impl Lsof for Proc {
+    fn exec(file_type: &FileType) -> Result<Vec<OpenFile>, Error> {
+        let context = Context::read(file_type)?;
+        let mut open_files: Vec<OpenFile> = vec![];
+        for pid in pids()? {
+            match read_process(pid, &context) {
+                Ok(files) => open_files.extend(files),
+                // the process exited or is not readable
+                Err(Error::IO(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(open_files)
+    }
+}
+
+impl Context {
+    pub fn read(file_type: &FileType) -> Result<Context, Error> {
+        let mut sockets = HashMap::new();
+        if matches!(file_type, FileType::NETWORK | FileType::ALL) {
+            for (table, _type, protocol) in INTERNET_SOCKET_TABLES {
+                // a missing table means the protocol is disabled
+                if let Ok(content) = fs::read_to_string(format!("{PROC_PATH}/net/{table}")) {
+                    sockets.extend(parse_socket_table(&content, _type, protocol)?);
+                }
+            }
+        }
+        let mut unix_sockets = HashMap::new();
+        if file_type != &FileType::NETWORK {
+            if let Ok(content) = fs::read_to_string(format!("{PROC_PATH}/net/unix")) {
+                unix_sockets = parse_unix_socket_table(&content)?;
+            }
+        }
+        // the unix_diag kernel module may be missing, sockets are then listed without peer
+        let unix_peers = if file_type == &FileType::IPC {
+            unix_peers().unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        Ok(Context {
+            file_type: file_type.clone(),
+            sockets,
+            unix_sockets,
+            unix_peers,
+            root_device: fs::metadata(match file_type {
+                FileType::SCOPE(scope) => scope.path.as_str(),
+                _ => "/",
+            })?
+            .dev(),
+            created_at: Local::now().timestamp_millis(),
+        })
+    }
+
+    fn with_regular(&self) -> bool {
+        matches!(
+            self.file_type,
+            FileType::REGULAR | FileType::SCOPE(_) | FileType::ALL
+        )
+    }
+
+    fn with_ipc(&self) -> bool {
+        self.file_type == FileType::IPC
+    }
+
+    // Files on the root filesystem, or on the scope filesystem and selected by the scope. Removed
+    // files are looked up on every filesystem, as `lsof +L1` does, and selected by their path only.
+    fn in_scope(&self, name: &str, metadata: &Metadata) -> bool {
+        let deleted = metadata.nlink() == 0;
+        match &self.file_type {
+            FileType::SCOPE(scope) if scope.deleted_only => {
+                let path = name.strip_suffix(DELETED_SUFFIX).unwrap_or(name);
+                metadata.is_file()
+                    && !path.starts_with(MEMFD_PREFIX)
+                    && scope.matches(path, deleted)
+            }
+            FileType::SCOPE(scope) => {
+                let path = match deleted {
+                    true => name.strip_suffix(DELETED_SUFFIX).unwrap_or(name),
+                    false => name,
+                };
+                metadata.dev() == self.root_device && scope.matches(path, deleted)
+            }
+            _ => metadata.dev() == self.root_device,
+        }
+    }
+}
+
+pub(crate) fn pids() -> Result<Vec<u32>, Error> {
+    Ok(fs::read_dir(PROC_PATH)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse().ok())
+        .collect())
+}
+
+pub(crate) fn read_process(pid: u32, context: &Context) -> Result<Vec<OpenFile>, Error> {
+    let command = fs::read_to_string(format!("{PROC_PATH}/{pid}/comm"))?;
+    let status = fs::read_to_string(format!("{PROC_PATH}/{pid}/status"))?;
+    let header = (pid, parse_uid(&status)?, command.trim_end().to_string());
+    let mut open_files: Vec<OpenFile> = vec![];
+
+    if context.with_regular() {
+        for (fd, link) in [("cwd", "cwd"), ("rtd", "root"), ("txt", "exe")] {
+            let path = format!("{PROC_PATH}/{pid}/{link}");
+            if let (Ok(name), Ok(metadata)) = (fs::read_link(&path), fs::metadata(&path)) {
+                let name = name.to_string_lossy();
+                if context.in_scope(&name, &metadata) {
+                    open_files.push(file_row(&header, fd, &name, &metadata, context));
+                }
+            }
+        }
+        open_files.extend(read_mapped_files(&header, context));
+    }
+
+    let mut fds: Vec<(u32, String)> = fs::read_dir(format!("{PROC_PATH}/{pid}/fd"))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let fd = entry.file_name().to_string_lossy().parse().ok()?;
+            let link = fs::read_link(entry.path()).ok()?;
+            Some((fd, link.to_string_lossy().to_string()))
+        })
+        .collect();
+    fds.sort();
+
+    for (fd, link) in fds {
+        let fd = fd.to_string();
+        if let Some(inode) = parse_socket_inode(&link) {
+            if let Some(socket) = context.sockets.get(&inode) {
+                open_files.push(socket_row(&header, &fd, socket, context));
+            } else if let Some(path) = context.unix_sockets.get(&inode) {
+                if context.with_ipc() {
+                    let mut open_file = unix_row(&header, &fd, inode, path, context);
+                    open_file.access = read_access(pid, &fd);
+                    open_files.push(open_file);
+                } else if let Some(metadata) = regular_metadata(path) {
+                    if context.in_scope(path, &metadata) {
+                        let mut open_file = file_row(&header, &fd, path, &metadata, context);
+                        open_file._type = "unix".to_string();
+                        open_file.node = inode.to_string();
+                        open_files.push(open_file);
+                    }
+                }
+            }
+        } else if let Some(inode) = parse_pipe_inode(&link).filter(|_| context.with_ipc()) {
+            let mut open_file = pipe_row(&header, &fd, inode, context);
+            open_file.access = read_access(pid, &fd);
+            open_files.push(open_file);
+        } else if context.with_regular() && link.starts_with('/') {
+            if let Ok(metadata) = fs::metadata(format!("{PROC_PATH}/{pid}/fd/{fd}")) {
+                if context.in_scope(&link, &metadata) {
+                    open_files.push(file_row(&header, &fd, &link, &metadata, context));
+                }
+            }
+        } else if context.with_ipc() && link.starts_with('/') {
+            if let Ok(metadata) = fs::metadata(format!("{PROC_PATH}/{pid}/fd/{fd}")) {
+                if metadata.file_type().is_fifo() {
+                    let mut open_file = file_row(&header, &fd, &link, &metadata, context);
+                    open_file.access = read_access(pid, &fd);
+                    open_files.push(open_file);
+                }
+            }
+        }
+    }
+
+    Ok(open_files)
+}
+
+// Mappings of other users' processes are not readable without ptrace rights, their descriptors
+// are still listed.
+fn read_mapped_files(header: &(u32, u32, String), context: &Context) -> Vec<OpenFile> {
+    let maps = fs::read_to_string(format!("{PROC_PATH}/{}/maps", header.0)).unwrap_or_default();
+    let executable = fs::read_link(format!("{PROC_PATH}/{}/exe", header.0)).ok();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut open_files: Vec<OpenFile> = vec![];
+    for path in parse_mapped_paths(&maps) {
+        if executable
+            .as_ref()
+            .is_some_and(|exe| exe.as_os_str() == path)
+            || !seen.insert(path)
+        {
+            continue;
+        }
+        if let Ok(metadata) = fs::metadata(path) {
+            if context.in_scope(path, &metadata) {
+                open_files.push(file_row(header, "mem", path, &metadata, context));
+            }
+        }
+    }
+    open_files
+}
+
+fn file_row(
+    header: &(u32, u32, String),
+    fd: &str,
+    name: &str,
+    metadata: &Metadata,
+    context: &Context,
+) -> OpenFile {
+    let file_type = metadata.file_type();
+    let mut open_file = OpenFile::new(header.0, header.1, &header.2);
+    open_file.fd = fd.to_string();
+    open_file._type = if file_type.is_file() {
+        "REG"
+    } else if file_type.is_dir() {
+        "DIR"
+    } else if file_type.is_char_device() {
+        "CHR"
+    } else if file_type.is_block_device() {
+        "BLK"
+    } else if file_type.is_fifo() {
+        "FIFO"
+    } else if file_type.is_socket() {
+        "sock"
+    } else {
+        "unknown"
+    }
+    .to_string();
+    open_file.device = format!("0x{:x}", metadata.dev());
+    if file_type.is_file() || file_type.is_dir() {
+        open_file.size = metadata.size();
+    }
+    open_file.node = metadata.ino().to_string();
+    open_file.link_count = Some(metadata.nlink());
+    open_file.name = name.to_string();
+    open_file.created_at = context.created_at;
+    open_file
+}
+
+fn socket_row(
+    header: &(u32, u32, String),
+    fd: &str,
+    socket: &Socket,
+    context: &Context,
+) -> OpenFile {
+    let mut open_file = OpenFile::new(header.0, header.1, &header.2);
+    open_file.fd = fd.to_string();
+    open_file._type = socket._type.clone();
+    open_file.protocol = Some(socket.protocol.clone());
+    open_file.tcp_state = socket.tcp_state.clone();
+    open_file.send_queue = Some(socket.send_queue);
+    open_file.recv_queue = Some(socket.recv_queue);
+    open_file.ip_source_address = Some(format_address(&socket.local.0));
+    open_file.ip_source_port = Some(format_port(socket.local.1));
+    open_file.name = format_endpoint(&socket.local);
+    if !socket.remote.0.is_unspecified() || socket.remote.1 != 0 {
+        open_file.ip_destination_address = Some(format_address(&socket.remote.0));
+        open_file.ip_destination_port = Some(format_port(socket.remote.1));
+        open_file.name = format!("{}->{}", open_file.name, format_endpoint(&socket.remote));
+    }
+    open_file.created_at = context.created_at;
+    open_file
+}
+
+fn unix_row(
+    header: &(u32, u32, String),
+    fd: &str,
+    inode: u64,
+    path: &str,
+    context: &Context,
+) -> OpenFile {
+    let mut open_file = OpenFile::new(header.0, header.1, &header.2);
+    open_file.fd = fd.to_string();
+    open_file._type = "unix".to_string();
+    open_file.node = inode.to_string();
+    open_file.name = path.to_string();
+    open_file.peer_node = context.unix_peers.get(&inode).map(|peer| peer.to_string());
+    open_file.created_at = context.created_at;
+    open_file
+}
+
+// Anonymous pipes are named as lsof does, both ends share the pipe inode.
+fn pipe_row(header: &(u32, u32, String), fd: &str, inode: u64, context: &Context) -> OpenFile {
+    let mut open_file = OpenFile::new(header.0, header.1, &header.2);
+    open_file.fd = fd.to_string();
+    open_file._type = "FIFO".to_string();
+    open_file.node = inode.to_string();
+    open_file.name = "pipe".to_string();
+    open_file.created_at = context.created_at;
+    open_file
+}
+
+// Only sockets bound to a filesystem path are regular files.
+fn regular_metadata(path: &str) -> Option<Metadata> {
+    path.starts_with('/')
+        .then(|| fs::metadata(path).ok())
+        .flatten()
+}
+
+fn read_access(pid: u32, fd: &str) -> Option<String> {
+    fs::read_to_string(format!("{PROC_PATH}/{pid}/fdinfo/{fd}"))
+        .ok()
+        .and_then(|fdinfo| parse_access(&fdinfo))
+}
+
+fn format_address(address: &IpAddr) -> String {
+    if address.is_unspecified() {
+        "*".to_string()
+    } else {
+        address.to_string()
+    }
+}
+
+fn format_port(port: u16) -> String {
+    if port == 0 {
+        "*".to_string()
+    } else {
+        port.to_string()
+    }
+}
+
+fn format_endpoint(endpoint: &(IpAddr, u16)) -> String {
+    match endpoint.0 {
+        IpAddr::V6(address) if !address.is_unspecified() => {
+            format!("[{}]:{}", address, format_port(endpoint.1))
+        }
+        address => format!("{}:{}", format_address(&address), format_port(endpoint.1)),
+    }
+}
+
+fn parse_uid(status: &str) -> Result<u32, Error> {
+    Ok(status
+        .lines()
+        .find(|line| line.starts_with("Uid:"))
+        .and_then(|line| line[4..].split_whitespace().next())
+        .ok_or_else(|| Error::ParseFile {
+            content: status.to_string(),
+        })?
+        .parse()?)
+}
+
+fn parse_socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+fn parse_pipe_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("pipe:[")?.strip_suffix(']')?.parse().ok()
+}
+
+fn parse_mapped_paths(maps: &str) -> Vec<&str> {
+    maps.lines()
+        .filter_map(|line| {
+            // address perms offset dev inode pathname
+            let mut fields = line.splitn(6, char::is_whitespace);
+            let inode = fields.nth(4)?;
+            let path = fields.next()?.trim_start();
+            if inode != "0" && path.starts_with('/') && !path.ends_with(" (deleted)") {
+                Some(path)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn parse_socket_table(
+    content: &str,
+    _type: &str,
+    protocol: &str,
+) -> Result<HashMap<u64, Socket>, Error> {
+    let mut sockets = HashMap::new();
+    for line in content.lines().skip(1) {
+        // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            return Err(Error::ParseFile {
+                content: line.to_string(),
+            });
+        }
+        let (send_queue, recv_queue) = fields[4].split_once(':').ok_or(Error::ParseFile {
+            content: line.to_string(),
+        })?;
+        let tcp_state = if protocol == "TCP" {
+            let code = usize::from_str_radix(fields[3], 16)?;
+            code.checked_sub(1)
+                .and_then(|index| TCP_STATES.get(index))
+                .map(|state| state.to_string())
+        } else {
+            None
+        };
+        sockets.insert(
+            fields[9].parse()?,
+            Socket {
+                _type: _type.to_string(),
+                protocol: protocol.to_string(),
+                local: parse_socket_address(fields[1])?,
+                remote: parse_socket_address(fields[2])?,
+                tcp_state,
+                send_queue: u64::from_str_radix(send_queue, 16)?,
+                recv_queue: u64::from_str_radix(recv_queue, 16)?,
+            },
+        );
+    }
+    Ok(sockets)
+}
+
+pub(crate) fn parse_unix_socket_table(content: &str) -> Result<HashMap<u64, String>, Error> {
+    let mut sockets = HashMap::new();
+    for line in content.lines().skip(1) {
+        // Num RefCount Protocol Flags Type St Inode Path
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 7 {
+            return Err(Error::ParseFile {
+                content: line.to_string(),
+            });
+        }
+        let path = fields.get(7).unwrap_or(&"");
+        sockets.insert(fields[6].parse()?, path.to_string());
+    }
+    Ok(sockets)
+}
+
+// Kernel addresses are printed as native-endian 32-bit words, ports as big-endian hex.
+pub(crate) fn parse_socket_address(address: &str) -> Result<(IpAddr, u16), Error> {
+    let invalid = || Error::ParseFile {
+        content: address.to_string(),
+    };
+    let (ip, port) = address.split_once(':').ok_or_else(invalid)?;
+    let port = u16::from_str_radix(port, 16)?;
+    let mut octets: Vec<u8> = vec![];
+    for index in (0..ip.len()).step_by(8) {
+        let word = ip.get(index..index + 8).ok_or_else(invalid)?;
+        octets.extend(u32::from_str_radix(word, 16)?.to_ne_bytes());
+    }
+    let ip = match octets.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3])),
+        16 => {
+            let mut buffer = [0u8; 16];
+            buffer.copy_from_slice(&octets);
+            IpAddr::V6(Ipv6Addr::from(buffer))
+        }
+        _ => return Err(invalid()),
+    };
+    Ok((ip, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lsof::scope::Scope;
+    use std::os::fd::AsRawFd;
+
+    fn create_tcp_table() -> String {
+        "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21345 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1538 0100007F:D2F0 08 00000000:0000001A 00:00000000 00000000  1000        0 48213 1 0000000000000000 20 4 30 10 -1
+"
+        .to_string()
+    }
+
+    fn create_context() -> Context {
+        Context {
+            file_type: FileType::NETWORK,
+            sockets: parse_socket_table(&create_tcp_table(), "IPv4", "TCP").unwrap(),
+            unix_sockets: HashMap::new(),
+            unix_peers: HashMap::from([(20133, 20134)]),
+            root_device: 0xfe00,
+            created_at: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_parse_socket_address_ipv4() {
+        let (ip, port) = parse_socket_address("0100007F:1538").unwrap();
+        assert_eq!(ip, IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+        assert_eq!(port, 5432);
+    }
+
+    #[test]
+    fn test_parse_socket_address_ipv6() {
+        let (ip, port) = parse_socket_address("00000000000000000000000001000000:0050").unwrap();
+        assert_eq!(ip, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(port, 80);
+    }
+
+    #[test]
+    fn test_parse_socket_address_invalid() {
+        assert!(parse_socket_address("0100007F").is_err());
+        assert!(parse_socket_address("0100:0016").is_err());
+    }
+
+    #[test]
+    fn test_parse_socket_table() {
+        let sockets = parse_socket_table(&create_tcp_table(), "IPv4", "TCP").unwrap();
+        assert_eq!(sockets.len(), 2);
+        let socket = sockets.get(&48213).unwrap();
+        assert_eq!(socket._type, "IPv4");
+        assert_eq!(socket.local.1, 5432);
+        assert_eq!(socket.remote.1, 54000);
+        assert_eq!(socket.tcp_state, Some("CLOSE_WAIT".to_string()));
+        assert_eq!(socket.recv_queue, 26);
+        assert_eq!(socket.send_queue, 0);
+        let listen = sockets.get(&21345).unwrap();
+        assert_eq!(listen.tcp_state, Some("LISTEN".to_string()));
+    }
+
+    #[test]
+    fn test_parse_socket_table_udp() {
+        let sockets = parse_socket_table(&create_tcp_table(), "IPv4", "UDP").unwrap();
+        let socket = sockets.get(&48213).unwrap();
+        assert_eq!(socket.protocol, "UDP");
+        assert_eq!(socket.tcp_state, None);
+        assert_eq!(socket.recv_queue, 26);
+    }
+
+    #[test]
+    fn test_parse_unix_socket_table() {
+        let content = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 20131 /run/systemd/notify
+0000000000000000: 00000002 00000000 00010000 0001 01 20132 @/org/kernel/udev
+0000000000000000: 00000003 00000000 00000000 0001 03 20133
+";
+        let sockets = parse_unix_socket_table(content).unwrap();
+        assert_eq!(sockets.len(), 3);
+        assert_eq!(sockets.get(&20131).unwrap(), "/run/systemd/notify");
+        assert_eq!(sockets.get(&20132).unwrap(), "@/org/kernel/udev");
+        assert_eq!(sockets.get(&20133).unwrap(), "");
+    }
+
+    #[test]
+    fn test_parse_socket_inode() {
+        assert_eq!(parse_socket_inode("socket:[48213]"), Some(48213));
+        assert_eq!(parse_socket_inode("pipe:[48213]"), None);
+        assert_eq!(parse_socket_inode("/dev/null"), None);
+    }
+
+    #[test]
+    fn test_in_scope_deleted() {
+        let directory = std::env::temp_dir();
+        let path = directory.join(format!("rstracer_scope_{}.log", std::process::id()));
+        let file = fs::File::create(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let fd = format!("{PROC_PATH}/self/fd/{}", file.as_raw_fd());
+        let name = fs::read_link(&fd).unwrap().to_string_lossy().to_string();
+        let metadata = fs::metadata(&fd).unwrap();
+        let scope = Scope::new(
+            directory.to_str().unwrap(),
+            &["*.log".to_string()],
+            &[],
+            true,
+        )
+        .unwrap();
+        let context = Context::read(&FileType::SCOPE(scope)).unwrap();
+        assert!(name.ends_with(DELETED_SUFFIX));
+        assert!(context.in_scope(&name, &metadata));
+        // the file is on another filesystem than the scope path
+        let context = Context {
+            root_device: metadata.dev().wrapping_add(1),
+            ..context
+        };
+        assert!(context.in_scope(&name, &metadata));
+        assert!(!context.in_scope("/memfd:rstracer.log (deleted)", &metadata));
+        let kept = fs::metadata(&directory).unwrap();
+        assert!(!context.in_scope(directory.to_str().unwrap(), &kept));
+    }
+
+    #[test]
+    fn test_parse_pipe_inode() {
+        assert_eq!(parse_pipe_inode("pipe:[31337]"), Some(31337));
+        assert_eq!(parse_pipe_inode("socket:[31337]"), None);
+    }
+
+    #[test]
+    fn test_unix_row() {
+        let context = create_context();
+        let open_file = unix_row(&(7, 0, "dockerd".to_string()), "4", 20133, "", &context);
+        assert_eq!(open_file._type, "unix");
+        assert_eq!(open_file.node, "20133");
+        assert_eq!(open_file.peer_node, Some("20134".to_string()));
+        let open_file = unix_row(
+            &(7, 0, "dockerd".to_string()),
+            "5",
+            20131,
+            "/run/x",
+            &context,
+        );
+        assert_eq!(open_file.name, "/run/x");
+        assert_eq!(open_file.peer_node, None);
+    }
+
+    #[test]
+    fn test_parse_mapped_paths() {
+        let maps = "55d0c0a00000-55d0c0a28000 r--p 00000000 fe:00 1835023                    /usr/bin/bash
+7f2a4c000000-7f2a4c028000 r--p 00000000 fe:00 395379                     /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2a4c200000-7f2a4c221000 rw-p 00000000 00:00 0                          [heap]
+7f2a4c300000-7f2a4c301000 rw-s 00000000 00:01 2048                       /memfd:shm (deleted)
+7ffd1c1f0000-7ffd1c211000 rw-p 00000000 00:00 0                          [stack]
+";
+        assert_eq!(
+            parse_mapped_paths(maps),
+            vec!["/usr/bin/bash", "/usr/lib/x86_64-linux-gnu/libc.so.6"]
+        );
+    }
+
+    #[test]
+    fn test_socket_row_listen() {
+        let context = create_context();
+        let socket = context.sockets.get(&21345).unwrap();
+        let open_file = socket_row(&(1, 0, "sshd".to_string()), "3", socket, &context);
+        assert_eq!(open_file._type, "IPv4");
+        assert_eq!(open_file.name, "*:22");
+        assert_eq!(open_file.ip_source_address, Some("*".to_string()));
+        assert_eq!(open_file.ip_source_port, Some("22".to_string()));
+        assert_eq!(open_file.ip_destination_address, None);
+        assert_eq!(open_file.ip_destination_port, None);
+        assert_eq!(open_file.created_at, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_socket_row_established() {
+        let context = create_context();
+        let socket = context.sockets.get(&48213).unwrap();
+        let open_file = socket_row(&(42, 1000, "psql".to_string()), "5", socket, &context);
+        assert_eq!(open_file.name, "127.0.0.1:5432->127.0.0.1:54000");
+        assert_eq!(open_file.ip_source_address, Some("127.0.0.1".to_string()));
+        assert_eq!(
+            open_file.ip_destination_address,
+            Some("127.0.0.1".to_string())
+        );
+        assert_eq!(open_file.ip_destination_port, Some("54000".to_string()));
+        assert_eq!(open_file.protocol, Some("TCP".to_string()));
+        assert_eq!(open_file.tcp_state, Some("CLOSE_WAIT".to_string()));
+        assert_eq!(open_file.recv_queue, Some(26));
+    }
+
+    #[test]
+    fn test_format_endpoint_ipv6() {
+        assert_eq!(
+            format_endpoint(&(IpAddr::V6(Ipv6Addr::LOCALHOST), 443)),
+            "[::1]:443"
+        );
+        assert_eq!(
+            format_endpoint(&(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0)),
+            "*:*"
+        );
+    }
+}
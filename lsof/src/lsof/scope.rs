@@ -0,0 +1,109 @@
+use crate::lsof::error::Error;
+use glob::Pattern;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+// Patterns are matched against the full file path, `*` also matches `/`.
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Scope {
+    pub path: String,          // Directory or mount point watched
+    pub include: Vec<Pattern>, // Keeps only files matching one of these patterns, if any
+    pub exclude: Vec<Pattern>, // Drops files matching one of these patterns
+    pub deleted_only: bool,    // Keeps only files removed from disk while open
+}
+
+impl Scope {
+    pub fn new(
+        path: &str,
+        include: &[String],
+        exclude: &[String],
+        deleted_only: bool,
+    ) -> Result<Scope, Error> {
+        Ok(Scope {
+            path: path.to_string(),
+            include: include
+                .iter()
+                .map(|pattern| Pattern::new(pattern))
+                .collect::<Result<Vec<Pattern>, _>>()?,
+            exclude: exclude
+                .iter()
+                .map(|pattern| Pattern::new(pattern))
+                .collect::<Result<Vec<Pattern>, _>>()?,
+            deleted_only,
+        })
+    }
+
+    pub fn root() -> Scope {
+        Scope {
+            path: "/".to_string(),
+            include: vec![],
+            exclude: vec![],
+            deleted_only: false,
+        }
+    }
+
+    // A path of another filesystem than its parent, lsof then lists every file of this filesystem.
+    pub fn is_mount_point(&self) -> Result<bool, Error> {
+        let path = Path::new(&self.path);
+        Ok(match path.parent() {
+            Some(parent) => fs::metadata(path)?.dev() != fs::metadata(parent)?.dev(),
+            None => true,
+        })
+    }
+
+    pub fn matches(&self, name: &str, deleted: bool) -> bool {
+        (!self.deleted_only || deleted)
+            && is_within(name, &self.path)
+            && (self.include.is_empty() || self.include.iter().any(|p| p.matches(name)))
+            && !self.exclude.iter().any(|p| p.matches(name))
+    }
+}
+
+fn is_within(name: &str, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    name.strip_prefix(path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_matches() {
+        let scope = Scope::new(
+            "/var/log",
+            &["*.log".to_string()],
+            &["/var/log/journal/*".to_string()],
+            false,
+        )
+        .unwrap();
+        assert!(scope.matches("/var/log/nginx/access.log", false));
+        assert!(!scope.matches("/var/log/syslog", false));
+        assert!(!scope.matches("/var/log/journal/system.log", false));
+        assert!(!scope.matches("/var/logs/app.log", false));
+        assert!(!scope.matches("/tmp/app.log", false));
+    }
+
+    #[test]
+    fn test_scope_deleted_only() {
+        let scope = Scope::new("/", &[], &[], true).unwrap();
+        assert!(scope.matches("/tmp/payload", true));
+        assert!(!scope.matches("/tmp/payload", false));
+        assert!(Scope::root().matches("/tmp/payload", false));
+    }
+
+    #[test]
+    fn test_scope_invalid_pattern() {
+        assert!(Scope::new("/", &["[".to_string()], &[], false).is_err());
+    }
+
+    #[test]
+    fn test_is_mount_point() {
+        assert!(Scope::root().is_mount_point().unwrap());
+        let source = format!("{}/src", env!("CARGO_MANIFEST_DIR"));
+        let scope = Scope::new(&source, &[], &[], false).unwrap();
+        assert!(!scope.is_mount_point().unwrap());
+    }
+}
@@ -1,7 +1,19 @@
 use crate::lsof::error::Error;
+use crate::lsof::ipc::{parse_unix_name, strip_endpoints};
+use crate::lsof::proc::{DELETED_SUFFIX, MEMFD_PREFIX};
+use crate::lsof::scope::Scope;
 use crate::lsof::{FileType, Lsof, OpenFile};
+use chrono::Local;
+use std::env::consts;
 use std::process::{Command, Output};
 
+// Process id, command, user id, then per file: descriptor, type, device, size, inode, name,
+// access mode, lock, offset, protocol and TCP information.
+const FIELDS: &str = "pcuftDsinaloPT";
+const IPC_TYPES: [&str; 3] = ["unix", "FIFO", "PIPE"];
+// Without the offset, lsof reports the size of descriptors, that is the space held by deleted files.
+const DELETED_FIELDS: &str = "pcuftDsinalk";
+
 pub struct Unix;
 
 impl Lsof for Unix {
@@ -13,6 +25,27 @@ impl Lsof for Unix {
             FileType::NETWORK => Ok(Self::parse_output(&String::from_utf8_lossy(
                 &Self::lsof_network()?.stdout,
             ))?),
+            FileType::IPC => Ok(Self::parse_output(&String::from_utf8_lossy(
+                &Self::lsof_ipc()?.stdout,
+            ))?
+            .into_iter()
+            .filter(|open_file| IPC_TYPES.contains(&open_file._type.as_str()))
+            .collect()),
+            FileType::SCOPE(scope) => Ok(Self::parse_output(&String::from_utf8_lossy(
+                &Self::lsof_scope(scope)?.stdout,
+            ))?
+            .into_iter()
+            // `+L1` already selected the deleted files
+            .filter(|open_file| {
+                let name = open_file
+                    .name
+                    .strip_suffix(DELETED_SUFFIX)
+                    .unwrap_or(&open_file.name);
+                scope.matches(name, scope.deleted_only)
+                    && (!scope.deleted_only
+                        || open_file._type == "REG" && !name.starts_with(MEMFD_PREFIX))
+            })
+            .collect()),
             FileType::ALL => {
                 let mut open_files =
                     Self::parse_output(&String::from_utf8_lossy(&Self::lsof_network()?.stdout))?;
@@ -28,24 +61,52 @@ impl Lsof for Unix {
 impl Unix {
     fn lsof_network() -> Result<Output, Error> {
         Ok(Command::new("lsof")
-            .args(["-F", "pcuftDsin", "-i"])
+            .args(["-F", FIELDS, "-Tqs", "-i"])
             .output()?)
     }
 
+    // lsof has no selection for pipes, every file is listed then filtered.
+    // On Linux, `+E` adds the other end of unix sockets to their name.
+    fn lsof_ipc() -> Result<Output, Error> {
+        let mut command = Command::new("lsof");
+        command.args(["-F", FIELDS]);
+        if consts::OS == "linux" {
+            command.arg("+E");
+        }
+        Ok(command.output()?)
+    }
+
+    // A mount point lists its whole filesystem, `+D` walks a directory recursively. Removed files are no
+    // longer in their directory, `+L1` lists them on every filesystem and the scope then selects its own.
+    fn lsof_scope(scope: &Scope) -> Result<Output, Error> {
+        let mut command = Command::new("lsof");
+        if scope.deleted_only {
+            return Ok(command.args(["-F", DELETED_FIELDS, "+L1"]).output()?);
+        }
+        command.args(["-F", FIELDS]);
+        if !scope.is_mount_point()? {
+            command.arg("+D");
+        }
+        Ok(command.arg(&scope.path).output()?)
+    }
+
     fn lsof_mount_file() -> Result<Output, Error> {
         Ok(Command::new("lsof")
-            .args(["-F", "pcuftDsin", "/"])
+            .args(["-F", FIELDS, "-Tqs", "/"])
             .output()?)
     }
 
     fn parse_output(output: &str) -> Result<Vec<OpenFile>, Error> {
+        let created_at = Local::now().timestamp_millis();
         let mut open_files: Vec<OpenFile> = vec![];
         let of_per_process: Vec<String> = split_of_per_process(output);
         for process in of_per_process {
             let rows_per_process: Vec<String> = split_process_per_rows(&process);
             let header = deserialize_header(&rows_per_process[0])?;
             for row in &rows_per_process[1..] {
-                open_files.push(row_to_struct(&header, row))
+                let mut open_file = row_to_struct(&header, row);
+                open_file.created_at = created_at;
+                open_files.push(open_file)
             }
         }
         Ok(open_files)
@@ -68,37 +129,129 @@ fn split_process_per_rows(of_per_process: &str) -> Vec<String> {
     }
 }
 
-fn deserialize_header(header: &str) -> Result<(u32, i16, String), Error> {
-    let headers: Vec<&str> = header.lines().collect();
-    let pid: u32 = headers[0].replace('p', "").parse()?;
-    let uid: i16 = headers[2][1..].parse()?;
-    let command: String = headers[1][1..].to_string();
+fn deserialize_header(header: &str) -> Result<(u32, u32, String), Error> {
+    let mut lines = header.lines();
+    let pid: u32 = lines.next().unwrap_or_default().replace('p', "").parse()?;
+    let mut uid: u32 = 0;
+    let mut command = String::new();
+    for (label, value) in lines.filter_map(split_field) {
+        match label {
+            'c' => command = value.to_string(),
+            // macOS prints some ids as negative values, such as -2 for nobody
+            'u' => uid = value.parse::<i64>()? as u32,
+            _ => {}
+        }
+    }
     Ok((pid, uid, command))
 }
 
-fn row_to_struct(header: &(u32, i16, String), row: &str) -> OpenFile {
-    let fields: Vec<&str> = row.lines().collect();
+fn row_to_struct(header: &(u32, u32, String), row: &str) -> OpenFile {
+    let mut lines = row.lines();
     let mut buffer_open_file: OpenFile = OpenFile::new(header.0, header.1, &header.2);
-    buffer_open_file.fd = fields[0].to_string();
-    for field in &fields[1..] {
-        match &field[..1] {
-            "t" => buffer_open_file._type = field[1..].to_string(),
-            "s" => buffer_open_file.size = field[1..].parse().unwrap(),
-            "i" => buffer_open_file.node = field[1..].to_string(),
-            "D" => buffer_open_file.device = field[1..].to_string(),
-            "n" => buffer_open_file.name = field[1..].to_string(),
-            other => panic!("invalid lsof field label {}", other),
+    buffer_open_file.fd = lines.next().unwrap_or_default().to_string();
+    for (label, value) in lines.filter_map(split_field) {
+        match label {
+            't' => buffer_open_file._type = value.to_string(),
+            's' => buffer_open_file.size = value.parse().unwrap_or_default(),
+            'i' => buffer_open_file.node = value.to_string(),
+            'D' => buffer_open_file.device = value.to_string(),
+            'n' => buffer_open_file.name = value.to_string(),
+            'a' => buffer_open_file.access = non_blank(value),
+            'l' => buffer_open_file.lock = non_blank(value),
+            'o' => buffer_open_file.offset = parse_offset(value),
+            'k' => buffer_open_file.link_count = value.parse().ok(),
+            'P' => buffer_open_file.protocol = non_blank(value),
+            'T' => decode_tcp_info(&mut buffer_open_file, value),
+            // fields of other lsof versions or options are skipped
+            _ => {}
         }
     }
+    decode_addresses(&mut buffer_open_file);
+    if buffer_open_file._type == "unix" {
+        let (name, peer_node) = parse_unix_name(&buffer_open_file.name);
+        buffer_open_file.name = name;
+        buffer_open_file.peer_node = peer_node;
+    } else if buffer_open_file._type == "FIFO" {
+        buffer_open_file.name = strip_endpoints(&buffer_open_file.name).to_string();
+    }
     buffer_open_file
 }
 
+fn split_field(line: &str) -> Option<(char, &str)> {
+    let label = line.chars().next()?;
+    Some((label, &line[label.len_utf8()..]))
+}
+
+fn non_blank(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+// lsof prefixes decimal offsets with 0t and hexadecimal ones with 0x
+fn parse_offset(value: &str) -> Option<u64> {
+    if let Some(decimal) = value.strip_prefix("0t") {
+        decimal.parse().ok()
+    } else if let Some(hexadecimal) = value.strip_prefix("0x") {
+        u64::from_str_radix(hexadecimal, 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+fn decode_tcp_info(open_file: &mut OpenFile, info: &str) {
+    match info.split_once('=') {
+        Some(("ST", state)) => open_file.tcp_state = non_blank(state),
+        Some(("QR", length)) => open_file.recv_queue = length.parse().ok(),
+        Some(("QS", length)) => open_file.send_queue = length.parse().ok(),
+        _ => {}
+    }
+}
+
+fn decode_addresses(open_file: &mut OpenFile) {
+    if !["IPV4", "IPV6"].contains(&open_file._type.to_uppercase().as_str()) {
+        return;
+    }
+    let mut endpoints = open_file.name.split("->");
+    if let Some((address, port)) = endpoints.next().and_then(split_endpoint) {
+        open_file.ip_source_address = Some(normalize_source_address(address));
+        open_file.ip_source_port = Some(port.to_string());
+    }
+    if let Some((address, port)) = endpoints.next().and_then(split_endpoint) {
+        open_file.ip_destination_address = Some(address.replace(['[', ']'], ""));
+        open_file.ip_destination_port = Some(port.to_string());
+    }
+}
+
+fn split_endpoint(endpoint: &str) -> Option<(&str, &str)> {
+    let is_valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-.*:[]".contains(c))
+    };
+    endpoint
+        .rsplit_once(':')
+        .filter(|(address, port)| is_valid(address) && is_valid(port))
+}
+
+fn normalize_source_address(address: &str) -> String {
+    if address.starts_with('[') {
+        address.replace(['[', ']'], "")
+    } else if address.contains([':', '-']) {
+        address.split('.').next().unwrap_or(address).to_string()
+    } else {
+        address.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::lsof::unix::{
-        deserialize_header, row_to_struct, split_of_per_process, split_process_per_rows,
+        decode_addresses, deserialize_header, parse_offset, row_to_struct, split_of_per_process,
+        split_process_per_rows, Unix,
     };
+    use crate::lsof::OpenFile;
 
     fn create_lsof_output() -> String {
         "p163
@@ -193,4 +346,117 @@ n/Library/Preferences/Logging/.plist-cache.DCgGV34s
         assert_eq!(open_file.node, "2");
         assert_eq!(open_file.name, "/");
     }
+
+    #[test]
+    fn test_row_to_struct_with_all_fields() {
+        let row = "3
+au
+l 
+tIPv4
+D0xffff8881
+o0t0
+i48213
+PTCP
+nlocalhost:5432->localhost:54000
+TST=ESTABLISHED
+TQR=12
+TQS=0
+TSO=SO_KEEPALIVE
+k1
+Zunconfined
+";
+        let open_file = row_to_struct(&(42, 1000, "postgres".to_string()), row);
+        assert_eq!(open_file.fd, "3");
+        assert_eq!(open_file._type, "IPv4");
+        assert_eq!(open_file.access, Some("u".to_string()));
+        assert_eq!(open_file.lock, None);
+        assert_eq!(open_file.offset, Some(0));
+        assert_eq!(open_file.size, 0);
+        assert_eq!(open_file.protocol, Some("TCP".to_string()));
+        assert_eq!(open_file.tcp_state, Some("ESTABLISHED".to_string()));
+        assert_eq!(open_file.recv_queue, Some(12));
+        assert_eq!(open_file.send_queue, Some(0));
+        assert_eq!(open_file.link_count, Some(1));
+        assert_eq!(open_file.ip_destination_port, Some("54000".to_string()));
+    }
+
+    #[test]
+    fn test_row_to_struct_with_invalid_fields() {
+        let row = "4\nsnot_a_size\n\nqunknown\nn/tmp/file\n";
+        let open_file = row_to_struct(&(42, 1000, "bash".to_string()), row);
+        assert_eq!(open_file.size, 0);
+        assert_eq!(open_file.name, "/tmp/file");
+    }
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("0t1024"), Some(1024));
+        assert_eq!(parse_offset("0x400"), Some(1024));
+        assert_eq!(parse_offset("1024"), Some(1024));
+        assert_eq!(parse_offset("0tabc"), None);
+    }
+
+    #[test]
+    fn test_parse_output_with_extra_process_fields() {
+        let output = "p42\ng42\nR1\ncbash\nu1000\nLvictor\nfcwd\nar\ntDIR\nn/home\n";
+        let open_files = Unix::parse_output(output).unwrap();
+        assert_eq!(open_files.len(), 1);
+        assert_eq!(open_files[0].command, "bash");
+        assert_eq!(open_files[0].uid, 1000);
+        assert_eq!(open_files[0].access, Some("r".to_string()));
+    }
+
+    #[test]
+    fn test_parse_output_with_unix_peers() {
+        let output = "p127\ncdockerd\nu0\nf6\nau\ntunix\ni925\n\
+            n/run/docker.sock type=STREAM ->INO=926 42,docker,3u\n\
+            p42\ncdocker\nu1000\nf3\nau\ntunix\ni926\n\
+            ntype=STREAM ->INO=925 127,dockerd,6u\n\
+            f4\naw\ntFIFO\ni31337\nnpipe 127,dockerd,7r\n";
+        let open_files = Unix::parse_output(output).unwrap();
+        assert_eq!(open_files.len(), 3);
+        assert_eq!(open_files[0].name, "/run/docker.sock");
+        assert_eq!(open_files[0].peer_node, Some("926".to_string()));
+        assert_eq!(open_files[1].name, "");
+        assert_eq!(open_files[1].peer_node, Some("925".to_string()));
+        assert_eq!(open_files[2].name, "pipe");
+        assert_eq!(open_files[0].created_at, open_files[2].created_at);
+    }
+
+    #[test]
+    fn test_decode_addresses() {
+        let mut open_file = OpenFile::new(1, 0, "sshd");
+        open_file._type = "IPv4".to_string();
+        open_file.name = "localhost:ssh->192.168.1.10:52044".to_string();
+        decode_addresses(&mut open_file);
+        assert_eq!(open_file.ip_source_address, Some("localhost".to_string()));
+        assert_eq!(open_file.ip_source_port, Some("ssh".to_string()));
+        assert_eq!(
+            open_file.ip_destination_address,
+            Some("192.168.1.10".to_string())
+        );
+        assert_eq!(open_file.ip_destination_port, Some("52044".to_string()));
+    }
+
+    #[test]
+    fn test_decode_addresses_ipv6_listen() {
+        let mut open_file = OpenFile::new(1, 0, "nginx");
+        open_file._type = "IPv6".to_string();
+        open_file.name = "[::1]:http".to_string();
+        decode_addresses(&mut open_file);
+        assert_eq!(open_file.ip_source_address, Some("::1".to_string()));
+        assert_eq!(open_file.ip_source_port, Some("http".to_string()));
+        assert_eq!(open_file.ip_destination_address, None);
+        assert_eq!(open_file.ip_destination_port, None);
+    }
+
+    #[test]
+    fn test_decode_addresses_regular_file() {
+        let mut open_file = OpenFile::new(1, 0, "bash");
+        open_file._type = "REG".to_string();
+        open_file.name = "/tmp/a:b".to_string();
+        decode_addresses(&mut open_file);
+        assert_eq!(open_file.ip_source_address, None);
+        assert_eq!(open_file.ip_source_port, None);
+    }
 }
@@ -1,5 +1,7 @@
 use network::capture::Capture;
+use network::options::CaptureOptions;
 use network::producer;
+use network::stats::StatsRegistry;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
@@ -17,22 +19,34 @@ async fn main() {
         .with_max_level(Level::INFO)
         .init();
 
-    let (sender, mut receiver): (Sender<Capture>, Receiver<Capture>) = channel(256);
+    let (sender, mut receiver): (Sender<Vec<Capture>>, Receiver<Vec<Capture>>) = channel(256);
     let stop_flag = Arc::new(AtomicBool::new(false));
     let stop_flag_clone = Arc::clone(&stop_flag);
 
-    let producer_task = tokio::spawn(async move { producer(sender, &stop_flag_clone).await });
+    let options = Arc::new(CaptureOptions::default());
+    let registry = Arc::new(StatsRegistry::default());
+    let registry_clone = Arc::clone(&registry);
+    let producer_task =
+        tokio::spawn(
+            async move { producer(sender, options, registry_clone, &stop_flag_clone).await },
+        );
 
     let stop_task = tokio::spawn(async move {
         signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
         stop_flag.store(true, Ordering::Release);
     });
 
-    while let Some(capture) = receiver.recv().await {
-        println!("{:?}", capture);
+    while let Some(batch) = receiver.recv().await {
+        for capture in batch {
+            println!("{:?}", capture);
+        }
     }
 
     let (producer_task_result, stop_task_result) = join!(producer_task, stop_task);
     producer_task_result.unwrap().unwrap();
     stop_task_result.unwrap();
+
+    for stats in registry.snapshot() {
+        println!("{:?}", stats);
+    }
 }
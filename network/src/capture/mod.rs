@@ -3,9 +3,9 @@ use crate::capture::data_link::DataLink;
 use crate::capture::network::Network;
 use crate::capture::transport::Transport;
 use crate::error::Error;
-use chrono::Local;
 use pcap::Device;
 use std::fmt;
+use std::sync::Arc;
 use tracing::debug;
 
 pub mod application;
@@ -23,9 +23,10 @@ pub enum Layer {
 
 #[derive(Debug, Clone)]
 pub struct Capture {
-    pub device: Device,
-    pub packet: Vec<u8>,
-    pub created_at: i64,
+    pub device: Arc<Device>,      // Shared by all the packets of an interface
+    pub length: usize,            // Captured bytes
+    pub created_at: i64,          // Packet header timestamp, in nanoseconds since the epoch
+    pub malformed: Option<Layer>, // First layer that could not be read
     pub data_link: Option<DataLink>,
     pub network: Option<Network>,
     pub transport: Option<Transport>,
@@ -48,11 +49,12 @@ impl fmt::Display for Layer {
 }
 
 impl Capture {
-    fn new(packet: &[u8], device: &Device) -> Capture {
+    fn new(packet: &[u8], device: &Arc<Device>, created_at: i64) -> Capture {
         Capture {
-            device: device.clone(),
-            packet: packet.to_vec(),
-            created_at: Local::now().timestamp_millis(),
+            device: Arc::clone(device),
+            length: packet.len(),
+            created_at,
+            malformed: None,
             data_link: None,
             network: None,
             transport: None,
@@ -60,8 +62,8 @@ impl Capture {
         }
     }
 
-    pub fn parse(packet: &[u8], device: &Device) -> Result<Capture, Error> {
-        let mut capture = Self::new(packet, device);
+    pub fn parse(packet: &[u8], device: &Arc<Device>, created_at: i64) -> Result<Capture, Error> {
+        let mut capture = Self::new(packet, device, created_at);
         match data_link::read_packet(packet) {
             Ok(data_link) => {
                 match network::read_packet(&data_link) {
@@ -70,31 +72,43 @@ impl Capture {
                             Ok(transport) => {
                                 match application::read_packet(&transport) {
                                     Ok(application) => capture.application = Some(application),
-                                    Err(error) => handle_error(error)?,
+                                    Err(error) => {
+                                        capture.malformed = handle_error(error, Layer::Application)?
+                                    }
                                 }
                                 capture.transport = Some(transport)
                             }
-                            Err(error) => handle_error(error)?,
+                            Err(error) => {
+                                capture.malformed = handle_error(error, Layer::Transport)?
+                            }
                         }
                         capture.network = Some(network)
                     }
-                    Err(error) => handle_error(error)?,
+                    Err(error) => capture.malformed = handle_error(error, Layer::Network)?,
                 }
                 capture.data_link = Some(data_link);
             }
-            Err(error) => handle_error(error)?,
+            Err(error) => capture.malformed = handle_error(error, Layer::DataLink)?,
         }
         Ok(capture)
     }
 }
 
-fn handle_error(error: Error) -> Result<(), Error> {
+// Unknown protocols stop the parsing silently, unreadable headers flag the packet as malformed.
+fn handle_error(error: Error, layer: Layer) -> Result<Option<Layer>, Error> {
     match error {
-        Error::UnimplementedError { .. } | Error::PacketParsing => {
+        Error::UnimplementedError { .. } => {
             debug!("{}", error);
-            Ok(())
+            Ok(None)
+        }
+        Error::PacketParsing => {
+            debug!("{} on layer {}", error, layer);
+            Ok(Some(layer))
         }
-        _ => Err(error),
+        _ => Err(Error::MalformedPacket {
+            layer,
+            reason: error.to_string(),
+        }),
     }
 }
 
@@ -266,9 +280,13 @@ pub mod tests {
 
     #[test]
     fn test_capture_parse() {
-        let device = Device::lookup().unwrap().unwrap();
+        let device = Arc::new(Device::lookup().unwrap().unwrap());
         let packet = create_packet();
-        let capture = Capture::parse(&packet, &device).unwrap();
+        let capture = Capture::parse(&packet, &device, 1726842675123456789).unwrap();
+        assert_eq!(capture.created_at, 1726842675123456789);
+        assert_eq!(capture.length, packet.len());
+        assert!(Arc::ptr_eq(&capture.device, &device));
+        assert_eq!(capture.malformed, None);
 
         let data_link = capture.data_link.unwrap();
         assert_eq!(data_link.protocol, DataLinkProtocol::Ethernet);
@@ -289,4 +307,14 @@ pub mod tests {
             "\u{6}taivem\u{3}com\0"
         );
     }
+
+    #[test]
+    fn test_capture_parse_malformed() {
+        let device = Arc::new(Device::lookup().unwrap().unwrap());
+        let packet = create_packet();
+        let capture = Capture::parse(&packet[..18], &device, 1726842675123456789).unwrap();
+        assert_eq!(capture.malformed, Some(Layer::Network));
+        assert!(capture.data_link.is_some());
+        assert!(capture.network.is_none());
+    }
 }
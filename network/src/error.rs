@@ -1,3 +1,4 @@
+use crate::capture::Layer;
 use std::io;
 use thiserror::Error;
 use tokio::task::JoinError;
@@ -9,10 +10,18 @@ pub enum Error {
     UnimplementedError { layer: String, protocol: String },
     #[error("Packet can't be read yet")]
     PacketParsing,
+    #[error("Malformed packet on layer {layer}: {reason}")]
+    MalformedPacket { layer: Layer, reason: String },
+    #[error("Invalid capture file {name}: {reason}")]
+    InvalidFile { name: String, reason: String },
+    #[error("Capture channel closed")]
+    ChannelClosed,
     #[error("IO error: {0}")]
     IO(#[from] io::Error),
     #[error("Pcap error: {0}")]
     Pcap(#[from] pcap::Error),
+    #[error("Glob pattern error: {0}")]
+    Glob(#[from] glob::PatternError),
     #[error("Join error: {0}")]
     Join(#[from] JoinError),
 }
@@ -0,0 +1,527 @@
+use crate::capture::Capture;
+use crate::error::Error;
+use crate::{send_batch, BATCH_SIZE};
+use pcap::Device;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
+use tracing::{error, info, warn};
+
+const PCAP_MICRO: u32 = 0xa1b2c3d4;
+const PCAP_NANO: u32 = 0xa1b23c4d;
+const PCAPNG_SECTION: [u8; 4] = [0x0a, 0x0d, 0x0d, 0x0a];
+const PCAPNG_BYTE_ORDER: u32 = 0x1a2b3c4d;
+const PCAPNG_INTERFACE: u32 = 1;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const OPTION_END: u16 = 0;
+const OPTION_IF_NAME: u16 = 2;
+const OPTION_IF_TSRESOL: u16 = 9;
+const MAX_BLOCK_LENGTH: usize = 16 * 1024 * 1024;
+const LINKTYPE_NULL: u16 = 0;
+pub const LINKTYPE_ETHERNET: u16 = 1;
+const LINKTYPE_RAW: u16 = 101;
+const LINKTYPE_LINUX_SLL: u16 = 113;
+const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packet {
+    pub interface: String,   // Capture interface, from pcapng or the file name
+    pub link_type: u16,      // Link-layer header type
+    pub timestamp: Duration, // Capture time since the epoch
+    pub data: Vec<u8>,       // Captured bytes
+}
+
+#[derive(Debug, Clone)]
+struct Interface {
+    name: String,
+    link_type: u16,
+    units_per_second: u128,
+}
+
+#[derive(Debug)]
+enum Format {
+    Pcap {
+        big_endian: bool,
+        link_type: u16,
+        units_per_second: u128,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+// Sequential reader of the pcap and pcapng formats, the interfaces of every pcapng section are kept.
+#[derive(Debug)]
+pub struct CaptureFile<R: Read> {
+    reader: R,
+    name: String,
+    format: Format,
+}
+
+impl CaptureFile<BufReader<File>> {
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        CaptureFile::new(BufReader::new(File::open(path)?), &name)
+    }
+}
+
+impl<R: Read> CaptureFile<R> {
+    pub fn new(reader: R, name: &str) -> Result<Self, Error> {
+        let mut file = CaptureFile {
+            reader,
+            name: name.to_string(),
+            format: Format::PcapNg {
+                big_endian: false,
+                interfaces: vec![],
+            },
+        };
+        let mut magic = [0u8; 4];
+        file.reader.read_exact(&mut magic)?;
+        if magic == PCAPNG_SECTION {
+            file.read_section()?;
+            return Ok(file);
+        }
+
+        let (big_endian, units_per_second) = match (u32_of(&magic, false), u32_of(&magic, true)) {
+            (PCAP_MICRO, _) => (false, 1_000_000),
+            (PCAP_NANO, _) => (false, 1_000_000_000),
+            (_, PCAP_MICRO) => (true, 1_000_000),
+            (_, PCAP_NANO) => (true, 1_000_000_000),
+            _ => return Err(invalid(name, "unknown magic number")),
+        };
+        // version, time zone, accuracy and snapshot length are not used
+        let mut header = [0u8; 20];
+        file.reader.read_exact(&mut header)?;
+        file.format = Format::Pcap {
+            big_endian,
+            link_type: u32_of(&header[16..20], big_endian) as u16,
+            units_per_second,
+        };
+        Ok(file)
+    }
+
+    pub fn next_packet(&mut self) -> Result<Option<Packet>, Error> {
+        match self.format {
+            Format::Pcap {
+                big_endian,
+                link_type,
+                units_per_second,
+            } => self.next_pcap_packet(big_endian, link_type, units_per_second),
+            Format::PcapNg { .. } => self.next_pcapng_packet(),
+        }
+    }
+
+    fn next_pcap_packet(
+        &mut self,
+        big_endian: bool,
+        link_type: u16,
+        units_per_second: u128,
+    ) -> Result<Option<Packet>, Error> {
+        let mut header = [0u8; 16];
+        if !fill(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+        let seconds = u32_of(&header[0..4], big_endian) as u128;
+        let fraction = u32_of(&header[4..8], big_endian) as u128;
+        let length = u32_of(&header[8..12], big_endian) as usize;
+        if length > MAX_BLOCK_LENGTH {
+            return Err(invalid(&self.name, "packet length too large"));
+        }
+        let mut data = vec![0u8; length];
+        self.reader.read_exact(&mut data)?;
+        Ok(Some(Packet {
+            interface: self.name.clone(),
+            link_type,
+            timestamp: to_duration(seconds * units_per_second + fraction, units_per_second),
+            data,
+        }))
+    }
+
+    fn next_pcapng_packet(&mut self) -> Result<Option<Packet>, Error> {
+        loop {
+            let mut header = [0u8; 4];
+            if !fill(&mut self.reader, &mut header)? {
+                return Ok(None);
+            }
+            if header == PCAPNG_SECTION {
+                self.read_section()?;
+                continue;
+            }
+
+            let Format::PcapNg {
+                big_endian,
+                ref mut interfaces,
+            } = self.format
+            else {
+                unreachable!()
+            };
+            let block_type = u32_of(&header, big_endian);
+            let mut length = [0u8; 4];
+            self.reader.read_exact(&mut length)?;
+            let length = u32_of(&length, big_endian) as usize;
+            if !(12..=MAX_BLOCK_LENGTH).contains(&length) || !length.is_multiple_of(4) {
+                return Err(invalid(&self.name, "invalid block length"));
+            }
+            let mut body = vec![0u8; length - 8];
+            self.reader.read_exact(&mut body)?;
+            let body = &body[..length - 12];
+
+            match block_type {
+                PCAPNG_INTERFACE if body.len() >= 8 => {
+                    let index = interfaces.len();
+                    interfaces.push(read_interface(body, big_endian, &self.name, index));
+                }
+                PCAPNG_ENHANCED_PACKET if body.len() >= 20 => {
+                    let interface = interfaces
+                        .get(u32_of(&body[0..4], big_endian) as usize)
+                        .ok_or_else(|| invalid(&self.name, "unknown interface"))?;
+                    let timestamp = ((u32_of(&body[4..8], big_endian) as u128) << 32)
+                        | u32_of(&body[8..12], big_endian) as u128;
+                    let length = u32_of(&body[12..16], big_endian) as usize;
+                    let data = body
+                        .get(20..20 + length)
+                        .ok_or_else(|| invalid(&self.name, "packet longer than its block"))?;
+                    return Ok(Some(Packet {
+                        interface: interface.name.clone(),
+                        link_type: interface.link_type,
+                        timestamp: to_duration(timestamp, interface.units_per_second),
+                        data: data.to_vec(),
+                    }));
+                }
+                _ => {} // simple packet, statistics, name resolution and custom blocks
+            }
+        }
+    }
+
+    // The byte order magic follows the block length, so the length is read after it.
+    fn read_section(&mut self) -> Result<(), Error> {
+        let mut header = [0u8; 8];
+        self.reader.read_exact(&mut header)?;
+        let big_endian = match (u32_of(&header[4..8], false), u32_of(&header[4..8], true)) {
+            (PCAPNG_BYTE_ORDER, _) => false,
+            (_, PCAPNG_BYTE_ORDER) => true,
+            _ => return Err(invalid(&self.name, "unknown byte order magic")),
+        };
+        let length = u32_of(&header[0..4], big_endian) as usize;
+        if !(28..=MAX_BLOCK_LENGTH).contains(&length) {
+            return Err(invalid(&self.name, "invalid section length"));
+        }
+        io::copy(
+            &mut (&mut self.reader).take(length as u64 - 12),
+            &mut io::sink(),
+        )?;
+        self.format = Format::PcapNg {
+            big_endian,
+            interfaces: vec![],
+        };
+        Ok(())
+    }
+}
+
+fn read_interface(body: &[u8], big_endian: bool, file_name: &str, index: usize) -> Interface {
+    let mut interface = Interface {
+        name: format!("{}:{}", file_name, index),
+        link_type: u16_of(&body[0..2], big_endian),
+        units_per_second: 1_000_000,
+    };
+    let mut options = &body[8..];
+    while options.len() >= 4 {
+        let code = u16_of(&options[0..2], big_endian);
+        let length = u16_of(&options[2..4], big_endian) as usize;
+        let Some(value) = options.get(4..4 + length) else {
+            break;
+        };
+        match code {
+            OPTION_END => break,
+            OPTION_IF_NAME => {
+                interface.name = String::from_utf8_lossy(value)
+                    .trim_end_matches('\0')
+                    .to_string()
+            }
+            OPTION_IF_TSRESOL if length == 1 => {
+                let units_per_second = if value[0] & 0x80 == 0 {
+                    10u128.checked_pow(value[0] as u32)
+                } else {
+                    2u128.checked_pow((value[0] & 0x7f) as u32)
+                };
+                if let Some(units_per_second) = units_per_second {
+                    interface.units_per_second = units_per_second;
+                }
+            }
+            _ => {}
+        }
+        options = options.get(4 + length.next_multiple_of(4)..).unwrap_or(&[]);
+    }
+    interface
+}
+
+fn to_duration(timestamp: u128, units_per_second: u128) -> Duration {
+    let seconds = timestamp / units_per_second;
+    let nanoseconds = timestamp % units_per_second * 1_000_000_000 / units_per_second;
+    Duration::new(seconds as u64, nanoseconds as u32)
+}
+
+// Returns false when the reader ends before the first byte, a partial read is an error.
+fn fill<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < buffer.len() {
+        match reader.read(&mut buffer[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+            Ok(length) => read += length,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(true)
+}
+
+fn u16_of(bytes: &[u8], big_endian: bool) -> u16 {
+    let bytes = [bytes[0], bytes[1]];
+    if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    }
+}
+
+fn u32_of(bytes: &[u8], big_endian: bool) -> u32 {
+    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+// Link types commonly met in captures, `tcpdump -i any` writes LINUX_SLL or LINUX_SLL2.
+fn link_type_name(link_type: u16) -> String {
+    match link_type {
+        LINKTYPE_NULL => "NULL (0)".to_string(),
+        LINKTYPE_ETHERNET => "ETHERNET (1)".to_string(),
+        LINKTYPE_RAW => "RAW (101)".to_string(),
+        LINKTYPE_LINUX_SLL => "LINUX_SLL (113)".to_string(),
+        LINKTYPE_LINUX_SLL2 => "LINUX_SLL2 (276)".to_string(),
+        link_type => link_type.to_string(),
+    }
+}
+
+fn invalid(name: &str, reason: &str) -> Error {
+    Error::InvalidFile {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+// Runs on a blocking thread, packets are sent in batches as fast as the channel is drained, with
+// their original capture time.
+pub fn replay(
+    paths: &[String],
+    sender: Sender<Vec<Capture>>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    for path in paths {
+        info!("replay file {}", path);
+        let mut file = CaptureFile::open(Path::new(path))?;
+        let mut devices: HashMap<String, Arc<Device>> = HashMap::new();
+        let mut batch: Vec<Capture> = Vec::with_capacity(BATCH_SIZE);
+        let mut sent = 0;
+        let mut skipped = 0;
+        let mut unsupported: HashMap<u16, usize> = HashMap::new();
+
+        while !stop_flag.load(Ordering::Relaxed) {
+            let packet = match file.next_packet() {
+                Ok(Some(packet)) => packet,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("{}: {}", path, e);
+                    break;
+                }
+            };
+            // the parser only decodes ethernet frames
+            if packet.link_type != LINKTYPE_ETHERNET {
+                let count = unsupported.entry(packet.link_type).or_default();
+                if *count == 0 {
+                    warn!(
+                        "{}: link type {} is not decoded, its packets are skipped",
+                        path,
+                        link_type_name(packet.link_type)
+                    );
+                }
+                *count += 1;
+                continue;
+            }
+            let device = devices
+                .entry(packet.interface.clone())
+                .or_insert_with(|| Arc::new(Device::from(packet.interface.as_str())));
+            match Capture::parse(&packet.data, device, packet.timestamp.as_nanos() as i64) {
+                Ok(capture) => batch.push(capture),
+                Err(e) => {
+                    warn!("{}: {}", path, e);
+                    skipped += 1;
+                }
+            }
+            if batch.len() >= BATCH_SIZE {
+                sent += batch.len();
+                if let Err(e) = send_batch(&sender, &mut batch) {
+                    error!("{}: {}", path, e);
+                    return Ok(());
+                }
+            }
+        }
+        sent += batch.len();
+        if let Err(e) = send_batch(&sender, &mut batch) {
+            error!("{}: {}", path, e);
+            return Ok(());
+        }
+
+        for (link_type, count) in &unsupported {
+            warn!(
+                "{}: {} packets of link type {} skipped",
+                path,
+                count,
+                link_type_name(*link_type)
+            );
+        }
+        if skipped > 0 {
+            warn!("{}: {} packets skipped", path, skipped);
+        }
+        info!("replay of {} done with {} packets", path, sent);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::tests::create_packet;
+    use std::io::Cursor;
+
+    fn create_pcap(magic: u32, big_endian: bool, packet: &[u8]) -> Vec<u8> {
+        let word = |value: u32| {
+            if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            }
+        };
+        let mut file = vec![];
+        file.extend(word(magic));
+        file.extend([0u8; 16]);
+        file.extend(word(LINKTYPE_ETHERNET as u32));
+        file.extend(word(1726842675));
+        file.extend(word(123456));
+        file.extend(word(packet.len() as u32));
+        file.extend(word(packet.len() as u32));
+        file.extend(packet);
+        file
+    }
+
+    fn create_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let mut body = body.to_vec();
+        body.resize(body.len().next_multiple_of(4), 0);
+        let length = (body.len() as u32 + 12).to_le_bytes();
+        let mut block = block_type.to_le_bytes().to_vec();
+        block.extend(length);
+        block.extend(body);
+        block.extend(length);
+        block
+    }
+
+    fn create_pcapng(packet: &[u8]) -> Vec<u8> {
+        let mut section = PCAPNG_BYTE_ORDER.to_le_bytes().to_vec();
+        section.extend([1, 0, 0, 0]);
+        section.extend(u64::MAX.to_le_bytes());
+        let mut interface = vec![];
+        interface.extend((LINKTYPE_ETHERNET as u32).to_le_bytes());
+        interface.extend(0u32.to_le_bytes());
+        interface.extend(OPTION_IF_NAME.to_le_bytes());
+        interface.extend(5u16.to_le_bytes());
+        interface.extend(b"eth0\0\0\0\0");
+        interface.extend(OPTION_IF_TSRESOL.to_le_bytes());
+        interface.extend(1u16.to_le_bytes());
+        interface.extend([9, 0, 0, 0]);
+        interface.extend([0u8; 4]);
+        let timestamp: u64 = 1726842675123456789;
+        let mut enhanced_packet = vec![];
+        enhanced_packet.extend(1u32.to_le_bytes());
+        enhanced_packet.extend(((timestamp >> 32) as u32).to_le_bytes());
+        enhanced_packet.extend((timestamp as u32).to_le_bytes());
+        enhanced_packet.extend((packet.len() as u32).to_le_bytes());
+        enhanced_packet.extend((packet.len() as u32).to_le_bytes());
+        enhanced_packet.extend(packet);
+
+        let mut file = create_block(u32::from_le_bytes(PCAPNG_SECTION), &section);
+        file.extend(create_block(PCAPNG_INTERFACE, &[113, 0, 0, 0, 0, 0, 0, 0]));
+        file.extend(create_block(PCAPNG_INTERFACE, &interface));
+        file.extend(create_block(5, &[0u8; 8]));
+        file.extend(create_block(PCAPNG_ENHANCED_PACKET, &enhanced_packet));
+        file
+    }
+
+    #[test]
+    fn test_read_pcap() {
+        let packet = create_packet();
+        for (magic, big_endian, nanoseconds) in [
+            (PCAP_MICRO, false, 123456000),
+            (PCAP_MICRO, true, 123456000),
+            (PCAP_NANO, false, 123456),
+        ] {
+            let bytes = create_pcap(magic, big_endian, &packet);
+            let mut file = CaptureFile::new(Cursor::new(bytes), "test.pcap").unwrap();
+            let read = file.next_packet().unwrap().unwrap();
+            assert_eq!(read.interface, "test.pcap");
+            assert_eq!(read.link_type, LINKTYPE_ETHERNET);
+            assert_eq!(read.timestamp, Duration::new(1726842675, nanoseconds));
+            assert_eq!(read.data, packet);
+            assert!(file.next_packet().unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_read_pcapng() {
+        let packet = create_packet();
+        let bytes = create_pcapng(&packet);
+        let mut file = CaptureFile::new(Cursor::new(bytes), "test.pcapng").unwrap();
+        let read = file.next_packet().unwrap().unwrap();
+        assert_eq!(read.interface, "eth0");
+        assert_eq!(read.link_type, LINKTYPE_ETHERNET);
+        assert_eq!(read.timestamp, Duration::new(1726842675, 123456789));
+        assert_eq!(read.data, packet);
+        assert!(file.next_packet().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(CaptureFile::new(Cursor::new(vec![0u8; 24]), "test").is_err());
+        let mut bytes = create_pcapng(&create_packet());
+        bytes.truncate(bytes.len() - 10);
+        let mut file = CaptureFile::new(Cursor::new(bytes), "test.pcapng").unwrap();
+        assert!(file.next_packet().is_err());
+    }
+
+    #[test]
+    fn test_link_type_name() {
+        assert_eq!(link_type_name(LINKTYPE_LINUX_SLL), "LINUX_SLL (113)");
+        assert_eq!(link_type_name(LINKTYPE_ETHERNET), "ETHERNET (1)");
+        assert_eq!(link_type_name(147), "147");
+    }
+
+    #[test]
+    fn test_to_duration() {
+        assert_eq!(
+            to_duration(1_500_000, 1_000_000),
+            Duration::new(1, 500_000_000)
+        );
+        assert_eq!(to_duration(3, 2), Duration::new(1, 500_000_000));
+    }
+}
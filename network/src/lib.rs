@@ -1,68 +1,182 @@
 use crate::capture::Capture;
 use crate::error::Error;
-use pcap::Device;
+use crate::options::CaptureOptions;
+use crate::stats::{InterfaceCounters, StatsRegistry};
+use pcap::{Device, PacketHeader, Precision};
 use std::collections::HashMap;
+use std::mem;
+use std::os::fd::AsRawFd;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::Sender;
 use tokio::task::JoinHandle;
-use tokio::time::sleep;
-use tracing::{error, info, warn};
+use tokio::time::{sleep, timeout};
+use tracing::{debug, error, info, warn};
 
 pub mod capture;
 pub mod error;
+pub mod file;
+pub mod options;
+pub mod stats;
 
+pub const BATCH_SIZE: usize = 256;
+pub const BATCH_TIMEOUT: Duration = Duration::from_millis(100);
+
+// The handle is nonblocking, its descriptor is polled by the runtime and every wait is bounded by
+// the batch timeout: a partial batch is never held longer and the stop flag is seen even when
+// libpcap doesn't signal readiness. Malformed packets and batches refused by a full channel are
+// counted and skipped, the capture goes on.
 pub async fn read_device(
-    device: Device,
-    sender: Sender<Capture>,
+    device: Arc<Device>,
+    options: Arc<CaptureOptions>,
+    sender: Sender<Vec<Capture>>,
+    counters: Arc<InterfaceCounters>,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<(), Error> {
     let device_name = device.name.clone();
     info!("read device {}", device_name);
 
-    let capture = pcap::Capture::from_device(device.clone())?
-        .timeout(100)
-        .open()?;
-
+    let (precision, fraction_unit) = if options.nanosecond {
+        (Precision::Nano, 1)
+    } else {
+        (Precision::Micro, 1_000)
+    };
+    let mut capture = pcap::Capture::from_device(Device::clone(&device))?
+        .timeout(BATCH_TIMEOUT.as_millis() as i32)
+        .precision(precision)
+        .promisc(options.promiscuous)
+        .immediate_mode(options.immediate);
+    if let Some(snaplen) = options.snaplen {
+        capture = capture.snaplen(snaplen);
+    }
+    if let Some(buffer_size) = options.buffer_size {
+        capture = capture.buffer_size(buffer_size);
+    }
+    let mut capture = capture.open()?;
+    if let Some(filter) = options.filter(&device_name) {
+        capture.filter(filter, true)?;
+    }
     let mut capture = capture.setnonblock()?;
+    let selectable = AsyncFd::with_interest(capture.as_raw_fd(), Interest::READABLE)?;
 
-    while !stop_flag.load(Ordering::Relaxed) {
-        match capture.next_packet() {
-            Ok(packet) => match Capture::parse(packet.data, &device) {
-                Ok(capture) => {
-                    if let Err(e) = sender.send(capture).await {
+    let mut batch: Vec<Capture> = Vec::with_capacity(BATCH_SIZE);
+    let mut sent_at = Instant::now();
+    let mut pcap_stats = capture.stats()?;
+
+    'capture: while !stop_flag.load(Ordering::Relaxed) {
+        let mut guard = match timeout(BATCH_TIMEOUT, selectable.readable()).await {
+            Ok(Ok(guard)) => Some(guard),
+            Ok(Err(e)) => {
+                warn!("{}: {}", &device_name, e);
+                break;
+            }
+            Err(_) => None,
+        };
+        while batch.len() < BATCH_SIZE {
+            match capture.next_packet() {
+                Ok(packet) => match Capture::parse(
+                    packet.data,
+                    &device,
+                    header_timestamp(packet.header, fraction_unit),
+                ) {
+                    Ok(capture) => {
+                        if let Some(layer) = &capture.malformed {
+                            counters.add_parse_failure(layer);
+                        }
+                        batch.push(capture)
+                    }
+                    Err(Error::MalformedPacket { layer, reason }) => {
+                        debug!("{}: {} on layer {}", &device_name, reason, layer);
+                        counters.add_parse_failure(&layer);
+                    }
+                    Err(e) => {
                         error!("{}: {}", &device_name, e);
-                        break;
+                        break 'capture;
+                    }
+                },
+                // nothing left to read, the descriptor is polled again
+                Err(pcap::Error::TimeoutExpired) => {
+                    if let Some(guard) = guard.as_mut() {
+                        guard.clear_ready();
                     }
-                }
-                Err(e) => {
-                    error!("{}: {}", &device_name, e);
                     break;
                 }
-            },
-            Err(e) => match e {
-                pcap::Error::TimeoutExpired => {}
-                _ => {
+                Err(e) => {
                     warn!("{}: {}", &device_name, e);
-                    break;
+                    break 'capture;
+                }
+            }
+        }
+        if batch.len() >= BATCH_SIZE || sent_at.elapsed() >= BATCH_TIMEOUT {
+            if let Err(e) = try_send_batch(&sender, &mut batch, &counters) {
+                error!("{}: {}", &device_name, e);
+                break;
+            }
+            match capture.stats() {
+                Ok(stats) => {
+                    counters.add_pcap_stats(&pcap_stats, &stats);
+                    pcap_stats = stats;
                 }
-            },
+                Err(e) => warn!("{}: {}", &device_name, e),
+            }
+            sent_at = Instant::now();
         }
-        sleep(Duration::from_millis(10)).await;
     }
     info!("producer on device {} stop gracefully", device_name);
     Ok(())
 }
 
-pub async fn producer(sender: Sender<Capture>, stop_flag: &Arc<AtomicBool>) -> Result<(), Error> {
+// Live capture can't wait for the sink, a batch that doesn't fit in the channel is dropped.
+fn try_send_batch(
+    sender: &Sender<Vec<Capture>>,
+    batch: &mut Vec<Capture>,
+    counters: &InterfaceCounters,
+) -> Result<(), Error> {
+    if !batch.is_empty() {
+        let batch = mem::replace(batch, Vec::with_capacity(BATCH_SIZE));
+        match sender.try_send(batch) {
+            Ok(()) => {}
+            Err(TrySendError::Full(batch)) => {
+                debug!("capture channel full, {} packets dropped", batch.len());
+                counters.add_channel_dropped(batch.len());
+            }
+            Err(TrySendError::Closed(_)) => return Err(Error::ChannelClosed),
+        }
+    }
+    Ok(())
+}
+
+fn send_batch(sender: &Sender<Vec<Capture>>, batch: &mut Vec<Capture>) -> Result<(), Error> {
+    if !batch.is_empty() {
+        let batch = mem::replace(batch, Vec::with_capacity(BATCH_SIZE));
+        sender
+            .blocking_send(batch)
+            .map_err(|_| Error::ChannelClosed)?;
+    }
+    Ok(())
+}
+
+pub async fn producer(
+    sender: Sender<Vec<Capture>>,
+    options: Arc<CaptureOptions>,
+    registry: Arc<StatsRegistry>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Result<(), Error> {
     let mut tasks: HashMap<String, JoinHandle<Result<(), Error>>> = HashMap::new();
 
     while !stop_flag.load(Ordering::Relaxed) {
         let devices: Vec<Device> = Device::list()?
             .into_iter()
-            .filter(|device| device.flags.is_up() && device.flags.is_running())
+            .filter(|device| {
+                device.flags.is_up()
+                    && device.flags.is_running()
+                    && options.is_captured(&device.name)
+            })
             .collect();
 
         for device in devices {
@@ -72,10 +186,13 @@ pub async fn producer(sender: Sender<Capture>, stop_flag: &Arc<AtomicBool>) -> R
                 is_running = !task.is_finished();
             }
             if !is_running {
+                let options = Arc::clone(&options);
                 let sender = sender.clone();
+                let counters = registry.counters(&device_name);
                 let stop_flag = Arc::clone(stop_flag);
-                let task =
-                    tokio::spawn(async move { read_device(device, sender, stop_flag).await });
+                let task = tokio::spawn(async move {
+                    read_device(Arc::new(device), options, sender, counters, stop_flag).await
+                });
                 tasks.insert(device_name, task);
             }
         }
@@ -88,6 +205,13 @@ pub async fn producer(sender: Sender<Capture>, stop_flag: &Arc<AtomicBool>) -> R
     Ok(())
 }
 
+// The kernel capture time, the header fraction being in microseconds or nanoseconds depending on
+// the requested precision. Field types of `timeval` differ between platforms.
+#[allow(clippy::unnecessary_cast)]
+fn header_timestamp(header: &PacketHeader, fraction_unit: i64) -> i64 {
+    header.ts.tv_sec as i64 * 1_000_000_000 + header.ts.tv_usec as i64 * fraction_unit
+}
+
 fn join_device_tasks(tasks: &HashMap<String, JoinHandle<Result<(), Error>>>) -> Result<(), Error> {
     for task in tasks.values() {
         if !task.is_finished() {
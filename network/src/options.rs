@@ -0,0 +1,101 @@
+use crate::error::Error;
+use glob::Pattern;
+use pcap::Linktype;
+use std::collections::HashMap;
+
+// Settings of the live capture handles, an interface filter replaces the global one.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    pub include: Vec<Pattern>,            // Captured interfaces, all if empty
+    pub exclude: Vec<Pattern>,            // Skipped interfaces
+    pub filter: Option<String>,           // BPF filter of every interface
+    pub filters: HashMap<String, String>, // BPF filter by interface name
+    pub snaplen: Option<i32>,             // Maximum bytes kept per packet
+    pub promiscuous: bool,                // Promiscuous mode
+    pub immediate: bool,                  // No kernel buffering of packets
+    pub buffer_size: Option<i32>,         // Kernel buffer size in bytes
+    pub nanosecond: bool,                 // Nanosecond header timestamps
+}
+
+impl CaptureOptions {
+    pub fn new(
+        include: &[String],
+        exclude: &[String],
+        filter: Option<String>,
+        filters: HashMap<String, String>,
+    ) -> Result<CaptureOptions, Error> {
+        let options = CaptureOptions {
+            include: include
+                .iter()
+                .map(|pattern| Pattern::new(pattern))
+                .collect::<Result<Vec<Pattern>, _>>()?,
+            exclude: exclude
+                .iter()
+                .map(|pattern| Pattern::new(pattern))
+                .collect::<Result<Vec<Pattern>, _>>()?,
+            filter,
+            filters,
+            ..Default::default()
+        };
+        // filters are compiled once here, so a typo fails at startup instead of in a device task
+        let dead = pcap::Capture::dead(Linktype::ETHERNET)?;
+        for filter in options.filter.iter().chain(options.filters.values()) {
+            dead.compile(filter, true)?;
+        }
+        Ok(options)
+    }
+
+    pub fn is_captured(&self, interface: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(interface)))
+            && !self.exclude.iter().any(|p| p.matches(interface))
+    }
+
+    pub fn filter(&self, interface: &str) -> Option<&str> {
+        self.filters
+            .get(interface)
+            .or(self.filter.as_ref())
+            .map(|filter| filter.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_captured() {
+        let options = CaptureOptions::new(
+            &[],
+            &["veth*".to_string(), "docker*".to_string()],
+            None,
+            HashMap::new(),
+        )
+        .unwrap();
+        assert!(options.is_captured("eth0"));
+        assert!(!options.is_captured("veth1a2b3c"));
+        assert!(!options.is_captured("docker0"));
+
+        let options =
+            CaptureOptions::new(&["eth*".to_string()], &[], None, HashMap::new()).unwrap();
+        assert!(options.is_captured("eth0"));
+        assert!(!options.is_captured("lo"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let filters = HashMap::from([("lo".to_string(), "udp".to_string())]);
+        let options =
+            CaptureOptions::new(&[], &[], Some("tcp port 443".to_string()), filters).unwrap();
+        assert_eq!(options.filter("eth0"), Some("tcp port 443"));
+        assert_eq!(options.filter("lo"), Some("udp"));
+        assert_eq!(CaptureOptions::default().filter("eth0"), None);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        assert!(CaptureOptions::new(&["[".to_string()], &[], None, HashMap::new()).is_err());
+        assert!(
+            CaptureOptions::new(&[], &[], Some("port port".to_string()), HashMap::new()).is_err()
+        );
+    }
+}
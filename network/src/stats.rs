@@ -0,0 +1,143 @@
+use crate::capture::Layer;
+use pcap::Stat;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+// Counters of an interface, kept across the restarts of its capture thread.
+#[derive(Debug, Default)]
+pub struct InterfaceCounters {
+    received: AtomicU64,
+    kernel_dropped: AtomicU64,
+    interface_dropped: AtomicU64,
+    parse_failures: [AtomicU64; 4], // By layer, from data link to application
+    channel_dropped: AtomicU64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureStats {
+    pub interface: String,         // Interface name
+    pub received: u64,             // Packets received by the capture handle
+    pub kernel_dropped: u64,       // Dropped for lack of kernel buffer space
+    pub interface_dropped: u64,    // Dropped by the interface or its driver
+    pub data_link_failures: u64,   // Malformed data link headers
+    pub network_failures: u64,     // Malformed network headers
+    pub transport_failures: u64,   // Malformed transport headers
+    pub application_failures: u64, // Malformed application payloads
+    pub channel_dropped: u64,      // Dropped because the capture channel was full
+}
+
+#[derive(Debug, Default)]
+pub struct StatsRegistry {
+    interfaces: Mutex<HashMap<String, Arc<InterfaceCounters>>>,
+}
+
+impl InterfaceCounters {
+    // pcap counters are cumulative per handle and 32 bits wide, only their increase is added.
+    pub fn add_pcap_stats(&self, previous: &Stat, current: &Stat) {
+        let delta = |previous: u32, current: u32| current.wrapping_sub(previous) as u64;
+        self.received.fetch_add(
+            delta(previous.received, current.received),
+            Ordering::Relaxed,
+        );
+        self.kernel_dropped
+            .fetch_add(delta(previous.dropped, current.dropped), Ordering::Relaxed);
+        self.interface_dropped.fetch_add(
+            delta(previous.if_dropped, current.if_dropped),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn add_parse_failure(&self, layer: &Layer) {
+        let index = match layer {
+            Layer::DataLink => 0,
+            Layer::Network => 1,
+            Layer::Transport => 2,
+            Layer::Application => 3,
+        };
+        self.parse_failures[index].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_channel_dropped(&self, count: usize) {
+        self.channel_dropped
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    fn stats(&self, interface: &str) -> CaptureStats {
+        CaptureStats {
+            interface: interface.to_string(),
+            received: self.received.load(Ordering::Relaxed),
+            kernel_dropped: self.kernel_dropped.load(Ordering::Relaxed),
+            interface_dropped: self.interface_dropped.load(Ordering::Relaxed),
+            data_link_failures: self.parse_failures[0].load(Ordering::Relaxed),
+            network_failures: self.parse_failures[1].load(Ordering::Relaxed),
+            transport_failures: self.parse_failures[2].load(Ordering::Relaxed),
+            application_failures: self.parse_failures[3].load(Ordering::Relaxed),
+            channel_dropped: self.channel_dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl StatsRegistry {
+    pub fn counters(&self, interface: &str) -> Arc<InterfaceCounters> {
+        let mut interfaces = self.interfaces.lock().unwrap();
+        Arc::clone(interfaces.entry(interface.to_string()).or_default())
+    }
+
+    pub fn snapshot(&self) -> Vec<CaptureStats> {
+        let interfaces = self.interfaces.lock().unwrap();
+        let mut stats: Vec<CaptureStats> = interfaces
+            .iter()
+            .map(|(interface, counters)| counters.stats(interface))
+            .collect();
+        stats.sort_by(|a, b| a.interface.cmp(&b.interface));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_pcap_stats() {
+        let registry = StatsRegistry::default();
+        let counters = registry.counters("eth0");
+        let first = Stat {
+            received: u32::MAX - 1,
+            dropped: 3,
+            if_dropped: 0,
+        };
+        let second = Stat {
+            received: 8,
+            dropped: 5,
+            if_dropped: 1,
+        };
+        counters.add_pcap_stats(&first, &second);
+        counters.add_pcap_stats(&second, &second);
+        let stats = registry.snapshot();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].interface, "eth0");
+        assert_eq!(stats[0].received, 10);
+        assert_eq!(stats[0].kernel_dropped, 2);
+        assert_eq!(stats[0].interface_dropped, 1);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let registry = StatsRegistry::default();
+        registry.counters("lo").add_channel_dropped(256);
+        let counters = registry.counters("eth0");
+        counters.add_parse_failure(&Layer::Network);
+        counters.add_parse_failure(&Layer::Network);
+        counters.add_parse_failure(&Layer::Application);
+        let stats = registry.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].interface, "eth0");
+        assert_eq!(stats[0].data_link_failures, 0);
+        assert_eq!(stats[0].network_failures, 2);
+        assert_eq!(stats[0].application_failures, 1);
+        assert_eq!(stats[1].interface, "lo");
+        assert_eq!(stats[1].channel_dropped, 256);
+    }
+}
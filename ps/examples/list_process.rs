@@ -1,7 +1,7 @@
-use ps::ps::{ps, Process};
+use ps::ps::{ps, Process, Source};
 
 pub fn main() {
-    display(ps().unwrap())
+    display(ps(&Source::COMMAND).unwrap())
 }
 
 fn display(processes: Vec<Process>) {
@@ -0,0 +1,15 @@
+use ps::ps::tree::ProcessTree;
+use ps::ps::{ps, Source};
+use std::process;
+
+pub fn main() {
+    let tree = ProcessTree::new(ps(&Source::COMMAND).unwrap());
+    let pid = process::id();
+    let ancestors: Vec<String> = tree
+        .ancestors(pid)
+        .iter()
+        .rev()
+        .map(|process| format!("{} ({})", process.command, process.pid))
+        .collect();
+    println!("{} > {}", ancestors.join(" > "), pid);
+}
@@ -0,0 +1,212 @@
+use crate::ps::proc::PROC_PATH;
+use crate::ps::Process;
+use std::fs;
+
+const CONTAINER_ID_LENGTH: usize = 64;
+const POD_UID_LENGTH: usize = 36;
+
+// Scope prefixes of the container runtimes with the systemd cgroup driver
+const RUNTIME_PREFIXES: [(&str, &str); 4] = [
+    ("cri-containerd-", "containerd"),
+    ("crio-", "cri-o"),
+    ("docker-", "docker"),
+    ("libpod-", "podman"),
+];
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+pub struct Cgroup {
+    pub path: String,                  // Cgroup path of the process
+    pub container_id: Option<String>,  // Container ID
+    pub pod_uid: Option<String>,       // Kubernetes pod UID
+    pub runtime: Option<String>,       // Container runtime
+    pub systemd_unit: Option<String>,  // Systemd service or scope
+    pub systemd_slice: Option<String>, // Systemd slice holding the unit
+}
+
+impl Cgroup {
+    pub fn read(pid: u32) -> Option<Cgroup> {
+        let content = fs::read_to_string(format!("{PROC_PATH}/{pid}/cgroup")).ok()?;
+        Self::parse(&content)
+    }
+
+    // The unified hierarchy is preferred, cgroup v1 hosts fall back on the systemd or the first hierarchy.
+    pub fn parse(content: &str) -> Option<Cgroup> {
+        let hierarchies: Vec<(&str, &str, &str)> = content
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, ':');
+                Some((parts.next()?, parts.next()?, parts.next()?))
+            })
+            .collect();
+        let path = hierarchies
+            .iter()
+            .find(|(id, controllers, _)| *id == "0" && controllers.is_empty())
+            .or_else(|| {
+                hierarchies
+                    .iter()
+                    .find(|(_, controllers, _)| *controllers == "name=systemd")
+            })
+            .or_else(|| hierarchies.first())
+            .map(|(_, _, path)| path.trim().to_string())?;
+
+        let mut cgroup = Cgroup {
+            path: path.clone(),
+            ..Cgroup::default()
+        };
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        for (i, segment) in segments.iter().enumerate() {
+            let name = segment.trim_end_matches(".scope");
+            if let Some((prefix, runtime)) = RUNTIME_PREFIXES
+                .iter()
+                .find(|(prefix, _)| name.starts_with(prefix))
+            {
+                if is_container_id(&name[prefix.len()..]) {
+                    cgroup.container_id = Some(name[prefix.len()..].to_string());
+                    cgroup.runtime = Some(runtime.to_string());
+                }
+            } else if is_container_id(name) {
+                // cgroupfs driver, the runtime is only known from the parent directory
+                cgroup.container_id = Some(name.to_string());
+                if i > 0 && segments[i - 1] == "docker" {
+                    cgroup.runtime = Some("docker".to_string());
+                }
+            } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+                cgroup.container_id = Some(name.to_string());
+                cgroup.runtime = Some("lxc".to_string());
+            } else if i > 0 && segments[i - 1] == "lxc" {
+                cgroup.container_id = Some(segment.to_string());
+                cgroup.runtime = Some("lxc".to_string());
+            }
+            if let Some(pod_uid) = parse_pod_uid(segment) {
+                cgroup.pod_uid = Some(pod_uid);
+            }
+            if segment.ends_with(".slice") {
+                cgroup.systemd_slice = Some(segment.to_string());
+            } else if segment.ends_with(".service") || segment.ends_with(".scope") {
+                cgroup.systemd_unit = Some(segment.to_string());
+            }
+        }
+        Some(cgroup)
+    }
+}
+
+// Reads the inode of a namespace, such as `net:[4026531840]`
+pub fn namespace(pid: u32, name: &str) -> Option<u64> {
+    let link = fs::read_link(format!("{PROC_PATH}/{pid}/ns/{name}")).ok()?;
+    parse_namespace(&link.to_string_lossy())
+}
+
+// `ps` has no container columns, the attribution is read from /proc when it is available
+pub(crate) fn attribute(process: &mut Process) {
+    process.cgroup = Cgroup::read(process.pid);
+    process.mnt_ns = namespace(process.pid, "mnt");
+    process.net_ns = namespace(process.pid, "net");
+}
+
+fn parse_namespace(link: &str) -> Option<u64> {
+    link.split_once(":[")?.1.strip_suffix(']')?.parse().ok()
+}
+
+fn parse_pod_uid(segment: &str) -> Option<String> {
+    let name = segment.trim_end_matches(".slice");
+    let uid = if let Some(uid) = name.strip_prefix("pod") {
+        uid
+    } else {
+        &name[name.rfind("-pod")? + 4..]
+    };
+    // the systemd driver replaces the dashes of the uid with underscores
+    let uid = uid.replace('_', "-");
+    (uid.len() == POD_UID_LENGTH && uid.chars().all(|c| c.is_ascii_hexdigit() || c == '-'))
+        .then_some(uid)
+}
+
+fn is_container_id(name: &str) -> bool {
+    name.len() == CONTAINER_ID_LENGTH && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER_ID: &str = "3f2c5d8e9a1b4c7d6e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d";
+
+    #[test]
+    fn test_parse_docker() {
+        let cgroup =
+            Cgroup::parse(&format!("0::/system.slice/docker-{CONTAINER_ID}.scope\n")).unwrap();
+        assert_eq!(cgroup.container_id, Some(CONTAINER_ID.to_string()));
+        assert_eq!(cgroup.runtime, Some("docker".to_string()));
+        assert_eq!(cgroup.pod_uid, None);
+        assert_eq!(cgroup.systemd_slice, Some("system.slice".to_string()));
+
+        let cgroup = Cgroup::parse(&format!("12:pids:/docker/{CONTAINER_ID}\n")).unwrap();
+        assert_eq!(cgroup.container_id, Some(CONTAINER_ID.to_string()));
+        assert_eq!(cgroup.runtime, Some("docker".to_string()));
+    }
+
+    #[test]
+    fn test_parse_kubernetes() {
+        let content = format!("0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod0f9a8b7c_6d5e_4f3a_2b1c_0d9e8f7a6b5c.slice/cri-containerd-{CONTAINER_ID}.scope\n");
+        let cgroup = Cgroup::parse(&content).unwrap();
+        assert_eq!(cgroup.container_id, Some(CONTAINER_ID.to_string()));
+        assert_eq!(cgroup.runtime, Some("containerd".to_string()));
+        assert_eq!(
+            cgroup.pod_uid,
+            Some("0f9a8b7c-6d5e-4f3a-2b1c-0d9e8f7a6b5c".to_string())
+        );
+
+        let content = format!(
+            "11:memory:/kubepods/besteffort/pod0f9a8b7c-6d5e-4f3a-2b1c-0d9e8f7a6b5c/{CONTAINER_ID}\n"
+        );
+        let cgroup = Cgroup::parse(&content).unwrap();
+        assert_eq!(cgroup.container_id, Some(CONTAINER_ID.to_string()));
+        assert_eq!(cgroup.runtime, None);
+        assert_eq!(
+            cgroup.pod_uid,
+            Some("0f9a8b7c-6d5e-4f3a-2b1c-0d9e8f7a6b5c".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_systemd() {
+        let content =
+            "12:pids:/system.slice/ssh.service\n1:name=systemd:/system.slice/ssh.service\n";
+        let cgroup = Cgroup::parse(content).unwrap();
+        assert_eq!(cgroup.path, "/system.slice/ssh.service");
+        assert_eq!(cgroup.container_id, None);
+        assert_eq!(cgroup.systemd_unit, Some("ssh.service".to_string()));
+        assert_eq!(cgroup.systemd_slice, Some("system.slice".to_string()));
+
+        let cgroup = Cgroup::parse("0::/user.slice/user-1000.slice/session-2.scope\n").unwrap();
+        assert_eq!(cgroup.systemd_unit, Some("session-2.scope".to_string()));
+        assert_eq!(cgroup.systemd_slice, Some("user-1000.slice".to_string()));
+    }
+
+    #[test]
+    fn test_parse_lxc() {
+        let cgroup = Cgroup::parse("0::/lxc.payload.web/system.slice/nginx.service\n").unwrap();
+        assert_eq!(cgroup.container_id, Some("web".to_string()));
+        assert_eq!(cgroup.runtime, Some("lxc".to_string()));
+        assert_eq!(cgroup.systemd_unit, Some("nginx.service".to_string()));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(Cgroup::parse(""), None);
+        let cgroup = Cgroup::parse("0::/\n").unwrap();
+        assert_eq!(
+            cgroup,
+            Cgroup {
+                path: "/".to_string(),
+                ..Cgroup::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_namespace() {
+        assert_eq!(parse_namespace("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_namespace("mnt:[]"), None);
+        assert_eq!(parse_namespace("invalid"), None);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::ps::error::Error;
+use crate::ps::proc::{parse_stat, pids, System, PROC_PATH};
+use chrono::Local;
+use std::collections::HashSet;
+use std::env::consts;
+use std::fs;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Variable {
+    pub pid: u32,        // Process ID
+    pub lstart: i64,     // Process start time, same as `Process.lstart`
+    pub key: String,     // Variable name
+    pub value: String,   // Variable value
+    pub created_at: i64, // Timestamp collection
+}
+
+// Reads the environment of the processes not seen by a previous call, it is fixed once the process has started.
+pub struct EnvironReader {
+    system: System,
+    seen: HashSet<(u32, i64)>,
+}
+
+impl EnvironReader {
+    pub fn new() -> Result<EnvironReader, Error> {
+        if consts::OS != "linux" {
+            return Err(Error::Unimplemented {
+                os: consts::OS.to_string(),
+                arch: consts::ARCH.to_string(),
+            });
+        }
+        Ok(EnvironReader {
+            system: System::read()?,
+            seen: HashSet::new(),
+        })
+    }
+
+    pub fn read_new(&mut self) -> Result<Vec<Variable>, Error> {
+        let created_at = Local::now().timestamp_millis();
+        let mut variables: Vec<Variable> = vec![];
+        let mut alive: HashSet<(u32, i64)> = HashSet::new();
+        for pid in pids()? {
+            // the process may exit between the listing and the read
+            let Ok(content) = fs::read_to_string(format!("{PROC_PATH}/{pid}/stat")) else {
+                continue;
+            };
+            let lstart = self.system.start_time(&parse_stat(&content)?);
+            alive.insert((pid, lstart));
+            if self.seen.contains(&(pid, lstart)) {
+                continue;
+            }
+            // the environment of other users is only readable with CAP_SYS_PTRACE
+            if let Ok(environ) = fs::read(format!("{PROC_PATH}/{pid}/environ")) {
+                variables.extend(parse_environ(&environ).into_iter().map(|(key, value)| {
+                    Variable {
+                        pid,
+                        lstart,
+                        key,
+                        value,
+                        created_at,
+                    }
+                }));
+            }
+        }
+        self.seen = alive;
+        Ok(variables)
+    }
+}
+
+fn parse_environ(environ: &[u8]) -> Vec<(String, String)> {
+    environ
+        .split(|byte| *byte == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (key, value) = entry.split_once('=')?;
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ() {
+        let environ = b"PATH=/usr/bin:/bin\0JAVA_OPTS=-Xmx1g -Da=b\0EMPTY=\0invalid\0=value\0";
+        assert_eq!(
+            parse_environ(environ),
+            vec![
+                ("PATH".to_string(), "/usr/bin:/bin".to_string()),
+                ("JAVA_OPTS".to_string(), "-Xmx1g -Da=b".to_string()),
+                ("EMPTY".to_string(), "".to_string()),
+            ]
+        );
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    #[test]
+    fn test_read_new() {
+        if consts::OS == "linux" {
+            let mut reader = EnvironReader::new().unwrap();
+            let pid = std::process::id();
+            let variables = reader.read_new().unwrap();
+            assert!(variables.iter().any(|variable| variable.pid == pid));
+            let variables = reader.read_new().unwrap();
+            assert!(variables.iter().all(|variable| variable.pid != pid));
+        } else {
+            assert!(EnvironReader::new().is_err());
+        }
+    }
+}
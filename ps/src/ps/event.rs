@@ -0,0 +1,429 @@
+use crate::ps::error::Error;
+use crate::ps::proc::{parse_cmdline, parse_stat, System, PROC_PATH};
+use chrono::Local;
+use std::collections::HashMap;
+use std::env::consts;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const NLMSG_HEADER_LENGTH: usize = 16;
+const CN_MSG_HEADER_LENGTH: usize = 20;
+const PROC_EVENT_HEADER_LENGTH: usize = 16;
+const PROC_EVENT_FORK: u32 = 0x00000001;
+const PROC_EVENT_EXEC: u32 = 0x00000002;
+const PROC_EVENT_EXIT: u32 = 0x80000000;
+const RECEIVE_BUFFER_SIZE: usize = 8192;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum EventType {
+    FORK,
+    EXEC,
+    EXIT,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct ProcessEvent {
+    pub event: EventType,        // Kernel event type
+    pub pid: u32,                // Process ID
+    pub ppid: Option<u32>,       // Parent Process ID
+    pub started_at: Option<i64>, // Process start time, same as `Process.lstart`
+    pub command: Option<String>, // Command with all its arguments
+    pub exit_code: Option<i32>,  // Exit status, 128 + signal number when killed by a signal
+    pub created_at: i64,         // Timestamp event reception
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RawEvent {
+    Fork { ppid: u32, pid: u32 },
+    Exec { pid: u32 },
+    Exit { pid: u32, exit_code: i32 },
+}
+
+// Subscribes to the process events of the Linux kernel connector. Requires CAP_NET_ADMIN.
+pub struct EventListener {
+    socket: i32,
+    system: System,
+    started_at: HashMap<u32, i64>, // Start time of the processes seen by fork or exec events
+    pruned_at: Instant,            // Last removal of the processes whose exit was missed
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            EventType::FORK => "fork",
+            EventType::EXEC => "exec",
+            EventType::EXIT => "exit",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl EventListener {
+    pub fn new(timeout: Duration) -> Result<EventListener, Error> {
+        if consts::OS != "linux" {
+            return Err(Error::Unimplemented {
+                os: consts::OS.to_string(),
+                arch: consts::ARCH.to_string(),
+            });
+        }
+        let system = System::read()?;
+        let socket = netlink::open(timeout)?;
+        Ok(EventListener {
+            socket,
+            system,
+            started_at: HashMap::new(),
+            pruned_at: Instant::now(),
+        })
+    }
+
+    // Waits for the next kernel message, returns no event when the timeout elapses.
+    pub fn receive(&mut self) -> Result<Vec<ProcessEvent>, Error> {
+        let mut buffer = [0u8; RECEIVE_BUFFER_SIZE];
+        let length = netlink::receive(self.socket, &mut buffer)?;
+        let created_at = Local::now().timestamp_millis();
+        // exit events are lost when the socket buffer overflows
+        if self.pruned_at.elapsed() >= PRUNE_INTERVAL {
+            prune_exited(&mut self.started_at);
+            self.pruned_at = Instant::now();
+        }
+        Ok(parse_message(&buffer[..length])
+            .into_iter()
+            .map(|raw_event| self.enrich(raw_event, created_at))
+            .collect())
+    }
+
+    fn enrich(&mut self, raw_event: RawEvent, created_at: i64) -> ProcessEvent {
+        let pid = match raw_event {
+            RawEvent::Fork { pid, .. } | RawEvent::Exec { pid } | RawEvent::Exit { pid, .. } => pid,
+        };
+        // the process may already be gone, its start time is then taken from a previous event
+        let stat = fs::read_to_string(format!("{PROC_PATH}/{pid}/stat"))
+            .ok()
+            .and_then(|content| parse_stat(&content).ok());
+        let started_at = stat
+            .as_ref()
+            .map(|stat| self.system.start_time(stat))
+            .or_else(|| self.started_at.get(&pid).copied());
+        let command = fs::read(format!("{PROC_PATH}/{pid}/cmdline"))
+            .ok()
+            .filter(|cmdline| !cmdline.is_empty())
+            .map(|cmdline| parse_cmdline(&cmdline, ""));
+        let mut event = ProcessEvent {
+            event: EventType::EXEC,
+            pid,
+            ppid: stat.as_ref().map(|stat| stat.ppid),
+            started_at,
+            command,
+            exit_code: None,
+            created_at,
+        };
+        match raw_event {
+            RawEvent::Fork { ppid, .. } => {
+                event.event = EventType::FORK;
+                event.ppid = Some(ppid);
+                // a forked process starts now
+                event.started_at = event.started_at.or(Some(created_at / 1000));
+            }
+            RawEvent::Exec { .. } => {}
+            RawEvent::Exit { exit_code, .. } => {
+                event.event = EventType::EXIT;
+                event.exit_code = Some(exit_code);
+                self.started_at.remove(&pid);
+                return event;
+            }
+        }
+        if let Some(started_at) = event.started_at {
+            self.started_at.insert(pid, started_at);
+        }
+        event
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        netlink::close(self.socket);
+    }
+}
+
+fn prune_exited(started_at: &mut HashMap<u32, i64>) {
+    started_at.retain(|pid, _| Path::new(&format!("{PROC_PATH}/{pid}")).exists());
+}
+
+// A datagram holds netlink messages, each wrapping a connector message with one process event.
+pub(crate) fn parse_message(buffer: &[u8]) -> Vec<RawEvent> {
+    let mut events: Vec<RawEvent> = vec![];
+    let mut offset = 0;
+    while offset + NLMSG_HEADER_LENGTH <= buffer.len() {
+        let length = read_u32(buffer, offset) as usize;
+        if length < NLMSG_HEADER_LENGTH || offset + length > buffer.len() {
+            break;
+        }
+        let message = &buffer[offset + NLMSG_HEADER_LENGTH..offset + length];
+        if message.len() >= CN_MSG_HEADER_LENGTH + PROC_EVENT_HEADER_LENGTH {
+            if let Some(event) = parse_event(&message[CN_MSG_HEADER_LENGTH..]) {
+                events.push(event);
+            }
+        }
+        offset += (length + 3) & !3;
+    }
+    events
+}
+
+// Thread events are ignored, only the thread group leader identifies a process.
+fn parse_event(proc_event: &[u8]) -> Option<RawEvent> {
+    let what = read_u32(proc_event, 0);
+    let data = &proc_event[PROC_EVENT_HEADER_LENGTH..];
+    match what {
+        PROC_EVENT_FORK if data.len() >= 16 => {
+            let (ppid, pid, tgid) = (read_u32(data, 4), read_u32(data, 8), read_u32(data, 12));
+            (pid == tgid).then_some(RawEvent::Fork { ppid, pid })
+        }
+        PROC_EVENT_EXEC if data.len() >= 8 => {
+            let (pid, tgid) = (read_u32(data, 0), read_u32(data, 4));
+            (pid == tgid).then_some(RawEvent::Exec { pid })
+        }
+        PROC_EVENT_EXIT if data.len() >= 12 => {
+            let (pid, tgid) = (read_u32(data, 0), read_u32(data, 4));
+            let exit_code = decode_exit_code(read_u32(data, 8));
+            (pid == tgid).then_some(RawEvent::Exit { pid, exit_code })
+        }
+        _ => None,
+    }
+}
+
+// The kernel reports a wait status, decoded with the shell convention
+fn decode_exit_code(status: u32) -> i32 {
+    let signal = status & 0x7f;
+    if signal == 0 {
+        ((status >> 8) & 0xff) as i32
+    } else {
+        128 + signal as i32
+    }
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buffer[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+#[cfg(target_os = "linux")]
+mod netlink {
+    use std::io;
+    use std::mem;
+    use std::time::Duration;
+
+    const CN_IDX_PROC: u32 = 1;
+    const CN_VAL_PROC: u32 = 1;
+    const NLMSG_DONE: u16 = 3;
+    const PROC_CN_MCAST_LISTEN: u32 = 1;
+
+    pub fn open(timeout: Duration) -> io::Result<i32> {
+        unsafe {
+            let socket = libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                libc::NETLINK_CONNECTOR,
+            );
+            if socket < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let result = configure(socket, timeout);
+            if result.is_err() {
+                libc::close(socket);
+            }
+            result.map(|_| socket)
+        }
+    }
+
+    unsafe fn configure(socket: i32, timeout: Duration) -> io::Result<()> {
+        let mut address: libc::sockaddr_nl = mem::zeroed();
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = CN_IDX_PROC;
+        if libc::bind(
+            socket,
+            &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+            mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        ) < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+
+        let timeval = libc::timeval {
+            tv_sec: timeout.as_secs() as libc::time_t,
+            tv_usec: timeout.subsec_micros() as libc::suseconds_t,
+        };
+        if libc::setsockopt(
+            socket,
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            &timeval as *const libc::timeval as *const libc::c_void,
+            mem::size_of::<libc::timeval>() as libc::socklen_t,
+        ) < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+
+        let message = subscription_message(PROC_CN_MCAST_LISTEN);
+        if libc::send(
+            socket,
+            message.as_ptr() as *const libc::c_void,
+            message.len(),
+            0,
+        ) < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn receive(socket: i32, buffer: &mut [u8]) -> io::Result<usize> {
+        let length = unsafe {
+            libc::recv(
+                socket,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+                0,
+            )
+        };
+        if length < 0 {
+            let error = io::Error::last_os_error();
+            match error.raw_os_error() {
+                // timeout, or events dropped because the socket buffer was full
+                Some(libc::EAGAIN) | Some(libc::EINTR) | Some(libc::ENOBUFS) => Ok(0),
+                _ => Err(error),
+            }
+        } else {
+            Ok(length as usize)
+        }
+    }
+
+    pub fn close(socket: i32) {
+        unsafe {
+            libc::close(socket);
+        }
+    }
+
+    fn subscription_message(operation: u32) -> Vec<u8> {
+        let payload_length = mem::size_of::<u32>();
+        let length = super::NLMSG_HEADER_LENGTH + super::CN_MSG_HEADER_LENGTH + payload_length;
+        let mut message: Vec<u8> = Vec::with_capacity(length);
+        // nlmsghdr
+        message.extend((length as u32).to_ne_bytes());
+        message.extend(NLMSG_DONE.to_ne_bytes());
+        message.extend(0u16.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes());
+        message.extend(std::process::id().to_ne_bytes());
+        // cn_msg
+        message.extend(CN_IDX_PROC.to_ne_bytes());
+        message.extend(CN_VAL_PROC.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes());
+        message.extend(0u32.to_ne_bytes());
+        message.extend((payload_length as u16).to_ne_bytes());
+        message.extend(0u16.to_ne_bytes());
+        message.extend(operation.to_ne_bytes());
+        message
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod netlink {
+    use std::io;
+    use std::time::Duration;
+
+    pub fn open(_timeout: Duration) -> io::Result<i32> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn receive(_socket: i32, _buffer: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    pub fn close(_socket: i32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_message(what: u32, data: &[u32]) -> Vec<u8> {
+        let length =
+            NLMSG_HEADER_LENGTH + CN_MSG_HEADER_LENGTH + PROC_EVENT_HEADER_LENGTH + data.len() * 4;
+        let mut message: Vec<u8> = vec![];
+        message.extend((length as u32).to_ne_bytes());
+        message.extend([0u8; NLMSG_HEADER_LENGTH - 4]);
+        message.extend([0u8; CN_MSG_HEADER_LENGTH]);
+        message.extend(what.to_ne_bytes());
+        message.extend([0u8; PROC_EVENT_HEADER_LENGTH - 4]);
+        data.iter()
+            .for_each(|value| message.extend(value.to_ne_bytes()));
+        message
+    }
+
+    #[test]
+    fn test_parse_message_fork() {
+        let message = create_message(PROC_EVENT_FORK, &[100, 100, 4242, 4242]);
+        assert_eq!(
+            parse_message(&message),
+            vec![RawEvent::Fork {
+                ppid: 100,
+                pid: 4242
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_message_thread_ignored() {
+        let message = create_message(PROC_EVENT_FORK, &[100, 100, 4243, 4242]);
+        assert!(parse_message(&message).is_empty());
+    }
+
+    #[test]
+    fn test_parse_message_exec_and_exit() {
+        let mut message = create_message(PROC_EVENT_EXEC, &[4242, 4242]);
+        message.extend(create_message(PROC_EVENT_EXIT, &[4242, 4242, 256, 17]));
+        assert_eq!(
+            parse_message(&message),
+            vec![
+                RawEvent::Exec { pid: 4242 },
+                RawEvent::Exit {
+                    pid: 4242,
+                    exit_code: 1
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_message_truncated() {
+        let message = create_message(PROC_EVENT_EXEC, &[4242, 4242]);
+        assert!(parse_message(&message[..20]).is_empty());
+        assert!(parse_message(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_prune_exited() {
+        let pid = std::process::id();
+        let mut started_at = HashMap::from([(pid, 1726842675), (u32::MAX, 1726842675)]);
+        prune_exited(&mut started_at);
+        assert_eq!(started_at.len(), 1);
+        assert!(started_at.contains_key(&pid));
+    }
+
+    #[test]
+    fn test_decode_exit_code() {
+        assert_eq!(decode_exit_code(0), 0);
+        assert_eq!(decode_exit_code(2 << 8), 2);
+        assert_eq!(decode_exit_code(9), 137);
+    }
+
+    #[test]
+    fn test_event_type_display() {
+        assert_eq!(EventType::FORK.to_string(), "fork");
+        assert_eq!(EventType::EXEC.to_string(), "exec");
+        assert_eq!(EventType::EXIT.to_string(), "exit");
+    }
+}
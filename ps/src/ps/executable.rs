@@ -0,0 +1,187 @@
+use crate::ps::error::Error;
+use crate::ps::proc::{parse_stat, pids, System, PROC_PATH};
+use chrono::Local;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env::consts;
+use std::fs;
+use std::fs::{File, Metadata};
+use std::io;
+use std::os::unix::fs::MetadataExt;
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Executable {
+    pub pid: u32,         // Process ID
+    pub lstart: i64,      // Process start time, same as `Process.lstart`
+    pub path: String,     // Executable path, without the deleted suffix
+    pub device: u64,      // Device of the running executable
+    pub inode: u64,       // Inode of the running executable
+    pub size: u64,        // Size in bytes
+    pub modified_at: i64, // Last modification time
+    pub sha256: String,   // Hex encoded SHA-256 of the content
+    pub deleted: bool,    // Executable removed from disk while running
+    pub replaced: bool,   // Path now leads to another file than the running one
+    pub created_at: i64,  // Timestamp collection
+}
+
+// File identity used to reuse a hash, a binary rewritten in place changes its modification time.
+type FileKey = (u64, u64, i64);
+
+// Hashes the executable of every process, a file is only read again when its identity changes.
+pub struct ExecutableHasher {
+    system: System,
+    seen: HashMap<(u32, i64), (FileKey, bool, bool)>,
+    hashes: HashMap<FileKey, String>,
+}
+
+impl ExecutableHasher {
+    pub fn new() -> Result<ExecutableHasher, Error> {
+        if consts::OS != "linux" {
+            return Err(Error::Unimplemented {
+                os: consts::OS.to_string(),
+                arch: consts::ARCH.to_string(),
+            });
+        }
+        Ok(ExecutableHasher {
+            system: System::read()?,
+            seen: HashMap::new(),
+            hashes: HashMap::new(),
+        })
+    }
+
+    // Processes started, re-executed or whose executable changed on disk since the previous call.
+    pub fn read_new(&mut self) -> Result<Vec<Executable>, Error> {
+        let created_at = Local::now().timestamp_millis();
+        let mut executables: Vec<Executable> = vec![];
+        let mut seen: HashMap<(u32, i64), (FileKey, bool, bool)> = HashMap::new();
+        let mut hashes: HashMap<FileKey, String> = HashMap::new();
+        for pid in pids()? {
+            // kernel threads have no executable, other users need CAP_SYS_PTRACE
+            match self.read_executable(pid, &mut hashes, created_at) {
+                Ok(executable) => {
+                    let process = (executable.pid, executable.lstart);
+                    let state = (
+                        (executable.device, executable.inode, executable.modified_at),
+                        executable.deleted,
+                        executable.replaced,
+                    );
+                    if self.seen.get(&process) != Some(&state) {
+                        executables.push(executable);
+                    }
+                    seen.insert(process, state);
+                }
+                Err(Error::IO(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.seen = seen;
+        self.hashes = hashes;
+        Ok(executables)
+    }
+
+    fn read_executable(
+        &self,
+        pid: u32,
+        hashes: &mut HashMap<FileKey, String>,
+        created_at: i64,
+    ) -> Result<Executable, Error> {
+        let stat = parse_stat(&fs::read_to_string(format!("{PROC_PATH}/{pid}/stat"))?)?;
+        let exe = format!("{PROC_PATH}/{pid}/exe");
+        let link = fs::read_link(&exe)?.to_string_lossy().to_string();
+        // the magic link still opens the running file once it is deleted
+        let metadata = fs::metadata(&exe)?;
+        let key = (metadata.dev(), metadata.ino(), metadata.mtime());
+        let sha256 = match hashes.get(&key).or_else(|| self.hashes.get(&key)) {
+            Some(sha256) => sha256.clone(),
+            None => hash(File::open(&exe)?)?,
+        };
+        hashes.insert(key, sha256.clone());
+        let (path, deleted) = match link.strip_suffix(DELETED_SUFFIX) {
+            Some(path) => (path.to_string(), true),
+            None => (link, false),
+        };
+        let replaced =
+            !deleted && is_replaced(&format!("{PROC_PATH}/{pid}/root"), &path, &metadata);
+        Ok(Executable {
+            pid,
+            lstart: self.system.start_time(&stat),
+            path,
+            device: metadata.dev(),
+            inode: metadata.ino(),
+            size: metadata.size(),
+            modified_at: metadata.mtime(),
+            sha256,
+            deleted,
+            replaced,
+            created_at,
+        })
+    }
+}
+
+// The path is resolved under the process root, a containerized process then sees its own image and
+// not a host file of the same path.
+fn is_replaced(root: &str, path: &str, running: &Metadata) -> bool {
+    fs::metadata(format!("{root}{path}"))
+        .is_ok_and(|current| (current.dev(), current.ino()) != (running.dev(), running.ino()))
+}
+
+fn hash(mut reader: impl io::Read) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut reader, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(
+            hash("abc".as_bytes()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hash(io::empty()).unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_is_replaced() {
+        let root = std::env::temp_dir().join(format!("rstracer_root_{}", std::process::id()));
+        fs::create_dir_all(root.join("usr/bin")).unwrap();
+        let path = root.join("usr/bin/python3");
+        fs::write(&path, "python").unwrap();
+        let other = root.join("python3.other");
+        fs::write(&other, "python").unwrap();
+        let root_path = root.to_str().unwrap();
+        let running = fs::metadata(&path).unwrap();
+        assert!(!is_replaced(root_path, "/usr/bin/python3", &running));
+        let running = fs::metadata(&other).unwrap();
+        assert!(is_replaced(root_path, "/usr/bin/python3", &running));
+        assert!(!is_replaced(root_path, "/usr/bin/missing", &running));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_read_new() {
+        if consts::OS == "linux" {
+            let mut hasher = ExecutableHasher::new().unwrap();
+            let pid = std::process::id();
+            let executables = hasher.read_new().unwrap();
+            let executable = executables
+                .iter()
+                .find(|executable| executable.pid == pid)
+                .unwrap();
+            assert_eq!(executable.sha256.len(), 64);
+            assert!(!executable.deleted);
+            let executables = hasher.read_new().unwrap();
+            assert!(executables.iter().all(|executable| executable.pid != pid));
+        } else {
+            assert!(ExecutableHasher::new().is_err());
+        }
+    }
+}
@@ -1,53 +1,99 @@
+use crate::ps::cgroup::Cgroup;
 use crate::ps::error::Error;
+use crate::ps::proc::Proc;
+use crate::ps::security::Security;
 use crate::ps::unix::Unix;
 use std::env::consts;
-use std::process::Output;
+use std::fmt;
+pub mod cgroup;
+pub mod environ;
 pub mod error;
+pub mod event;
+pub mod executable;
+pub mod proc;
+pub mod security;
+pub mod thread;
+pub mod tree;
 pub mod unix;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum Source {
+    COMMAND, // `ps` command output
+    PROC,    // Linux /proc filesystem
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
 pub struct Process {
-    pub pid: u32,        // Process ID
-    pub ppid: u32,       // Parent Process ID
-    pub uid: i16,        // User ID of the process owner
-    pub lstart: i64,     // Exact date and time when the process started
-    pub pcpu: f32,       // CPU usage percentage
-    pub pmem: f32,       // Memory usage percentage
-    pub status: String,  // Process status
-    pub command: String, // Command with all its arguments
-    pub created_at: i64, // Timestamp command execution
+    pub pid: u32,                   // Process ID
+    pub ppid: u32,                  // Parent Process ID
+    pub uid: u32,                   // Real user ID of the process owner
+    pub euid: u32,                  // Effective user ID
+    pub gid: u32,                   // Real group ID
+    pub egid: u32,                  // Effective group ID
+    pub lstart: i64,                // Exact date and time when the process started
+    pub pcpu: f32,                  // CPU usage percentage
+    pub pmem: f32,                  // Memory usage percentage
+    pub utime: Option<u64>,         // Cumulative user CPU time in milliseconds (proc source)
+    pub stime: Option<u64>,         // Cumulative system CPU time in milliseconds (proc source)
+    pub rss: u64,                   // Resident set size in bytes
+    pub vsz: u64,                   // Virtual memory size in bytes
+    pub threads: Option<u32>,       // Number of threads (proc source)
+    pub nice: i32,                  // Nice value
+    pub priority: i32,              // Kernel scheduling priority
+    pub tty: Option<String>,        // Controlling terminal
+    pub exe: Option<String>,        // Executable path (proc source)
+    pub read_bytes: Option<u64>,    // Bytes read from storage (proc source)
+    pub write_bytes: Option<u64>,   // Bytes written to storage (proc source)
+    pub cgroup: Option<Cgroup>,     // Cgroup with its container or systemd attribution (Linux)
+    pub mnt_ns: Option<u64>,        // Mount namespace inode (Linux)
+    pub net_ns: Option<u64>,        // Network namespace inode (Linux)
+    pub security: Option<Security>, // Capabilities, seccomp and LSM label (Linux)
+    pub status: String,             // Process status
+    pub command: String,            // Command with all its arguments
+    pub created_at: i64,            // Timestamp command execution
 }
 
-pub trait Ps {
-    fn os_command() -> Result<Output, Error>;
-    fn parse_output(output: &str) -> Result<Vec<Process>, Error> {
-        let mut processes: Vec<Process> = vec![];
-        for row in output.lines().skip(1) {
-            if let Ok(process) = Self::parse_row(row) {
-                processes.push(process)
-            } else {
-                return Err(Error::ParseProcess {
-                    process: row.to_string(),
-                });
-            }
-        }
-        Ok(processes)
-    }
-    fn parse_row(row: &str) -> Result<Process, Error>;
-    fn parse_date(date_chunks: &[&str]) -> Result<i64, Error>;
-    fn exec() -> Result<Vec<Process>, Error> {
-        let output = Self::os_command()?;
-        Self::parse_output(&String::from_utf8_lossy(&output.stdout))
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Source::COMMAND => "command",
+            Source::PROC => "proc",
+        };
+        write!(f, "{}", s)
     }
 }
 
-pub fn ps() -> Result<Vec<Process>, Error> {
-    if ["linux", "macos"].contains(&consts::OS) {
-        Unix::exec()
-    } else {
-        Err(Error::Unimplemented {
+pub trait Ps {
+    fn exec() -> Result<Vec<Process>, Error>;
+}
+
+pub fn ps(source: &Source) -> Result<Vec<Process>, Error> {
+    match source {
+        Source::COMMAND if ["linux", "macos"].contains(&consts::OS) => Unix::exec(),
+        Source::PROC if consts::OS == "linux" => Proc::exec(),
+        _ => Err(Error::Unimplemented {
             os: consts::OS.to_string(),
             arch: consts::ARCH.to_string(),
-        })
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ps::{ps, Source};
+    use std::env::consts;
+
+    #[test]
+    fn test_ps() {
+        assert!(!ps(&Source::COMMAND).unwrap().is_empty());
+        if consts::OS == "linux" {
+            assert!(!ps(&Source::PROC).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_source_display() {
+        assert_eq!(Source::COMMAND.to_string(), "command");
+        assert_eq!(Source::PROC.to_string(), "proc");
     }
 }
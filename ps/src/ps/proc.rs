@@ -0,0 +1,367 @@
+use crate::ps::cgroup::{namespace, Cgroup};
+use crate::ps::error::Error;
+use crate::ps::security::{read_label, Security};
+use crate::ps::{Process, Ps};
+use chrono::Local;
+use std::fs;
+
+pub(crate) const PROC_PATH: &str = "/proc";
+
+pub struct Proc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct System {
+    pub boot_time: i64,    // Boot time in seconds since epoch
+    pub uptime: f64,       // Seconds since boot
+    pub memory_total: u64, // Total usable memory in kB
+    pub clock_ticks: u64,  // Clock ticks per second
+    pub page_size: u64,    // Memory page size in bytes
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Stat {
+    pub pid: u32,
+    pub comm: String,
+    pub state: char,
+    pub ppid: u32,
+    pub pgrp: i32,
+    pub session: i32,
+    pub tty_nr: i32,
+    pub tpgid: i32,
+    pub utime: u64,
+    pub stime: u64,
+    pub priority: i64,
+    pub nice: i64,
+    pub num_threads: u64,
+    pub starttime: u64,
+    pub vsize: u64,
+    pub rss: i64,
+    pub processor: Option<u32>,
+}
+
+impl Ps for Proc {
+    fn exec() -> Result<Vec<Process>, Error> {
+        let system = System::read()?;
+        let created_at = Local::now().timestamp_millis();
+        let mut processes: Vec<Process> = vec![];
+        for pid in pids()? {
+            match read_process(pid, &system, created_at) {
+                Ok(process) => processes.push(process),
+                // the process exited between the listing and the read
+                Err(Error::IO(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(processes)
+    }
+}
+
+impl System {
+    pub fn read() -> Result<System, Error> {
+        let stat = fs::read_to_string(format!("{PROC_PATH}/stat"))?;
+        let uptime = fs::read_to_string(format!("{PROC_PATH}/uptime"))?;
+        let meminfo = fs::read_to_string(format!("{PROC_PATH}/meminfo"))?;
+        Ok(System {
+            boot_time: parse_key_value(&stat, "btime").unwrap_or("0").parse()?,
+            uptime: uptime.split_whitespace().next().unwrap_or("0").parse()?,
+            memory_total: parse_key_value(&meminfo, "MemTotal:")
+                .unwrap_or("0")
+                .parse()?,
+            clock_ticks: sysconf(libc::_SC_CLK_TCK, 100),
+            page_size: sysconf(libc::_SC_PAGESIZE, 4096),
+        })
+    }
+
+    // Truncated to the second as `ps -o lstart` does, both add the start ticks to `btime`.
+    pub fn start_time(&self, stat: &Stat) -> i64 {
+        self.boot_time + (stat.starttime / self.clock_ticks) as i64
+    }
+
+    pub fn ticks_to_millis(&self, ticks: u64) -> u64 {
+        ticks * 1000 / self.clock_ticks
+    }
+}
+
+fn sysconf(name: libc::c_int, default: u64) -> u64 {
+    let value = unsafe { libc::sysconf(name) };
+    if value > 0 {
+        value as u64
+    } else {
+        default
+    }
+}
+
+pub(crate) fn pids() -> Result<Vec<u32>, Error> {
+    Ok(fs::read_dir(PROC_PATH)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_string_lossy().parse().ok())
+        .collect())
+}
+
+pub(crate) fn read_process(pid: u32, system: &System, created_at: i64) -> Result<Process, Error> {
+    let stat = parse_stat(&fs::read_to_string(format!("{PROC_PATH}/{pid}/stat"))?)?;
+    let status = fs::read_to_string(format!("{PROC_PATH}/{pid}/status"))?;
+    let cmdline = fs::read(format!("{PROC_PATH}/{pid}/cmdline"))?;
+    let (uid, euid) = parse_ids(&status, "Uid:")?;
+    let (gid, egid) = parse_ids(&status, "Gid:")?;
+    // io counters are only readable for processes owned by the current user
+    let io = fs::read_to_string(format!("{PROC_PATH}/{pid}/io")).ok();
+    let io = io.as_deref();
+    Ok(Process {
+        pid: stat.pid,
+        ppid: stat.ppid,
+        uid,
+        euid,
+        gid,
+        egid,
+        lstart: system.start_time(&stat),
+        pcpu: cpu_usage(&stat, system),
+        pmem: memory_usage(&stat, system),
+        utime: Some(system.ticks_to_millis(stat.utime)),
+        stime: Some(system.ticks_to_millis(stat.stime)),
+        rss: stat.rss.max(0) as u64 * system.page_size,
+        vsz: stat.vsize,
+        threads: Some(stat.num_threads as u32),
+        nice: stat.nice as i32,
+        priority: stat.priority as i32,
+        tty: tty_name(stat.tty_nr),
+        exe: fs::read_link(format!("{PROC_PATH}/{pid}/exe"))
+            .ok()
+            .map(|path| path.to_string_lossy().to_string()),
+        read_bytes: io.and_then(|io| parse_key_value(io, "read_bytes:")?.parse().ok()),
+        write_bytes: io.and_then(|io| parse_key_value(io, "write_bytes:")?.parse().ok()),
+        cgroup: Cgroup::read(pid),
+        mnt_ns: namespace(pid, "mnt"),
+        net_ns: namespace(pid, "net"),
+        security: Security::parse_status(&status).map(|security| Security {
+            lsm_label: read_label(pid),
+            ..security
+        }),
+        status: process_status(&stat),
+        command: parse_cmdline(&cmdline, &stat.comm),
+        created_at,
+    })
+}
+
+pub(crate) fn parse_stat(content: &str) -> Result<Stat, Error> {
+    let invalid = || Error::ParseProcess {
+        process: content.to_string(),
+    };
+    // comm is enclosed in parentheses and may itself contain spaces or parentheses
+    let comm_start = content.find('(').ok_or_else(invalid)?;
+    let comm_end = content.rfind(')').ok_or_else(invalid)?;
+    let fields: Vec<&str> = content[comm_end + 1..].split_whitespace().collect();
+    if fields.len() < 22 {
+        return Err(invalid());
+    }
+    Ok(Stat {
+        pid: content[..comm_start].trim().parse()?,
+        comm: content[comm_start + 1..comm_end].to_string(),
+        state: fields[0].chars().next().ok_or_else(invalid)?,
+        ppid: fields[1].parse()?,
+        pgrp: fields[2].parse()?,
+        session: fields[3].parse()?,
+        tty_nr: fields[4].parse()?,
+        tpgid: fields[5].parse()?,
+        utime: fields[11].parse()?,
+        stime: fields[12].parse()?,
+        priority: fields[15].parse()?,
+        nice: fields[16].parse()?,
+        num_threads: fields[17].parse()?,
+        starttime: fields[19].parse()?,
+        vsize: fields[20].parse()?,
+        rss: fields[21].parse()?,
+        // missing on old kernels
+        processor: fields.get(36).and_then(|processor| processor.parse().ok()),
+    })
+}
+
+fn parse_key_value<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    content
+        .lines()
+        .find(|line| line.starts_with(key))
+        .and_then(|line| line[key.len()..].split_whitespace().next())
+}
+
+// Returns the real and effective ids of a `Uid:` or `Gid:` status line
+fn parse_ids(status: &str, key: &str) -> Result<(u32, u32), Error> {
+    let ids: Vec<&str> = status
+        .lines()
+        .find(|line| line.starts_with(key))
+        .map(|line| line[key.len()..].split_whitespace().collect())
+        .unwrap_or_default();
+    if ids.len() < 2 {
+        return Err(Error::ParseProcess {
+            process: status.to_string(),
+        });
+    }
+    Ok((ids[0].parse()?, ids[1].parse()?))
+}
+
+pub(crate) fn parse_cmdline(cmdline: &[u8], comm: &str) -> String {
+    let args: Vec<String> = cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect();
+    if args.is_empty() {
+        format!("[{}]", comm)
+    } else {
+        args.join(" ")
+    }
+}
+
+// Decodes the device number of the controlling terminal, see devices.txt in the kernel documentation
+fn tty_name(tty_nr: i32) -> Option<String> {
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    match major {
+        0 => None,
+        4 if minor < 64 => Some(format!("tty{minor}")),
+        4 => Some(format!("ttyS{}", minor - 64)),
+        136..=143 => Some(format!("pts/{}", (major - 136) * 256 + minor)),
+        _ => Some(format!("{major}:{minor}")),
+    }
+}
+
+fn process_status(stat: &Stat) -> String {
+    let mut status = stat.state.to_string();
+    if stat.nice < 0 {
+        status.push('<');
+    } else if stat.nice > 0 {
+        status.push('N');
+    }
+    if stat.session == stat.pid as i32 {
+        status.push('s');
+    }
+    if stat.num_threads > 1 {
+        status.push('l');
+    }
+    if stat.pgrp == stat.tpgid {
+        status.push('+');
+    }
+    status
+}
+
+fn cpu_usage(stat: &Stat, system: &System) -> f32 {
+    let elapsed = system.uptime - stat.starttime as f64 / system.clock_ticks as f64;
+    if elapsed > 0.0 {
+        let cpu_time = (stat.utime + stat.stime) as f64 / system.clock_ticks as f64;
+        (cpu_time * 100.0 / elapsed) as f32
+    } else {
+        0.0
+    }
+}
+
+fn memory_usage(stat: &Stat, system: &System) -> f32 {
+    if system.memory_total > 0 {
+        let rss = stat.rss.max(0) as u64 * system.page_size / 1024;
+        (rss as f64 * 100.0 / system.memory_total as f64) as f32
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_stat() -> String {
+        "1234 (tmux: server (1)) S 1 1234 1234 0 -1 4194560 1042 0 0 0 250 150 0 0 20 0 1 0 \
+        1000 11186176 1024 18446744073709551615 1 1 0 0 0 0 0 4096 134295555 0 0 0 17 3 0 0 0 0 0"
+            .to_string()
+    }
+
+    fn create_system() -> System {
+        System {
+            boot_time: 1_700_000_000,
+            uptime: 110.0,
+            memory_total: 8192,
+            clock_ticks: 100,
+            page_size: 4096,
+        }
+    }
+
+    #[test]
+    fn test_parse_stat() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(stat.pid, 1234);
+        assert_eq!(stat.comm, "tmux: server (1)");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.session, 1234);
+        assert_eq!(stat.utime, 250);
+        assert_eq!(stat.stime, 150);
+        assert_eq!(stat.tty_nr, 0);
+        assert_eq!(stat.priority, 20);
+        assert_eq!(stat.nice, 0);
+        assert_eq!(stat.num_threads, 1);
+        assert_eq!(stat.starttime, 1000);
+        assert_eq!(stat.vsize, 11186176);
+        assert_eq!(stat.rss, 1024);
+        assert_eq!(stat.processor, Some(3));
+    }
+
+    #[test]
+    fn test_tty_name() {
+        assert_eq!(tty_name(0), None);
+        assert_eq!(tty_name(34816), Some("pts/0".to_string()));
+        assert_eq!(tty_name(34819), Some("pts/3".to_string()));
+        assert_eq!(tty_name(1025), Some("tty1".to_string()));
+        assert_eq!(tty_name(1088), Some("ttyS0".to_string()));
+    }
+
+    #[test]
+    fn test_parse_stat_invalid() {
+        assert!(parse_stat("1234 (bash) S 1").is_err());
+        assert!(parse_stat("").is_err());
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        let status = "Name:\tsudo\nUid:\t1000\t0\t0\t0\nGid:\t65534\t100\t100\t100\n";
+        assert_eq!(parse_ids(status, "Uid:").unwrap(), (1000, 0));
+        assert_eq!(parse_ids(status, "Gid:").unwrap(), (65534, 100));
+        assert!(parse_ids(status, "Pid:").is_err());
+    }
+
+    #[test]
+    fn test_parse_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"/usr/bin/python3\0/home/user/script.py\0", "python3"),
+            "/usr/bin/python3 /home/user/script.py"
+        );
+        assert_eq!(parse_cmdline(b"", "kthreadd"), "[kthreadd]");
+    }
+
+    #[test]
+    fn test_process_status() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(process_status(&stat), "Ss");
+    }
+
+    #[test]
+    fn test_start_time() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(create_system().start_time(&stat), 1_700_000_010);
+    }
+
+    #[test]
+    fn test_ticks_to_millis() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(create_system().ticks_to_millis(stat.utime), 2500);
+        assert_eq!(create_system().ticks_to_millis(stat.stime), 1500);
+    }
+
+    #[test]
+    fn test_cpu_usage() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(cpu_usage(&stat, &create_system()), 4.0);
+    }
+
+    #[test]
+    fn test_memory_usage() {
+        let stat = parse_stat(&create_stat()).unwrap();
+        assert_eq!(memory_usage(&stat, &create_system()), 50.0);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::ps::proc::PROC_PATH;
+use crate::ps::Process;
+use std::fs;
+
+// Capability names indexed by bit, from linux/capability.h
+const CAPABILITIES: [&str; 41] = [
+    "chown",
+    "dac_override",
+    "dac_read_search",
+    "fowner",
+    "fsetid",
+    "kill",
+    "setgid",
+    "setuid",
+    "setpcap",
+    "linux_immutable",
+    "net_bind_service",
+    "net_broadcast",
+    "net_admin",
+    "net_raw",
+    "ipc_lock",
+    "ipc_owner",
+    "sys_module",
+    "sys_rawio",
+    "sys_chroot",
+    "sys_ptrace",
+    "sys_pacct",
+    "sys_admin",
+    "sys_boot",
+    "sys_nice",
+    "sys_resource",
+    "sys_time",
+    "sys_tty_config",
+    "mknod",
+    "lease",
+    "audit_write",
+    "audit_control",
+    "setfcap",
+    "mac_override",
+    "mac_admin",
+    "syslog",
+    "wake_alarm",
+    "block_suspend",
+    "audit_read",
+    "perfmon",
+    "bpf",
+    "checkpoint_restore",
+];
+
+#[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+pub struct Security {
+    pub cap_effective: u64,        // Effective capability set
+    pub cap_permitted: u64,        // Permitted capability set
+    pub cap_bounding: u64,         // Bounding capability set
+    pub no_new_privs: bool,        // Privileges can not be gained through execve
+    pub seccomp: String,           // Seccomp mode: disabled, strict or filter
+    pub lsm_label: Option<String>, // SELinux context or AppArmor profile
+}
+
+impl Security {
+    pub fn read(pid: u32) -> Option<Security> {
+        let status = fs::read_to_string(format!("{PROC_PATH}/{pid}/status")).ok()?;
+        let mut security = Self::parse_status(&status)?;
+        security.lsm_label = read_label(pid);
+        Some(security)
+    }
+
+    pub fn parse_status(status: &str) -> Option<Security> {
+        let value = |key: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(key))
+                .map(|value| value.trim())
+        };
+        Some(Security {
+            cap_effective: u64::from_str_radix(value("CapEff:")?, 16).ok()?,
+            cap_permitted: u64::from_str_radix(value("CapPrm:")?, 16).ok()?,
+            cap_bounding: u64::from_str_radix(value("CapBnd:")?, 16).ok()?,
+            no_new_privs: value("NoNewPrivs:") == Some("1"),
+            seccomp: match value("Seccomp:") {
+                Some("1") => "strict",
+                Some("2") => "filter",
+                _ => "disabled",
+            }
+            .to_string(),
+            lsm_label: None,
+        })
+    }
+
+    pub fn effective_names(&self) -> Vec<&'static str> {
+        capability_names(self.cap_effective)
+    }
+}
+
+pub fn capability_names(mask: u64) -> Vec<&'static str> {
+    CAPABILITIES
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| mask & (1 << bit) != 0)
+        .map(|(_, name)| *name)
+        .collect()
+}
+
+// Only readable when a LSM is enabled
+pub(crate) fn read_label(pid: u32) -> Option<String> {
+    let attr = fs::read(format!("{PROC_PATH}/{pid}/attr/current")).ok()?;
+    parse_label(&attr)
+}
+
+pub(crate) fn attribute(process: &mut Process) {
+    process.security = Security::read(process.pid);
+}
+
+fn parse_label(attr: &[u8]) -> Option<String> {
+    let label = String::from_utf8_lossy(attr)
+        .trim_end_matches(['\0', '\n'])
+        .to_string();
+    (!label.is_empty()).then_some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status() {
+        let status = "Name:\tsshd\nUid:\t0\t0\t0\t0\nCapInh:\t0000000000000000\nCapPrm:\t000001ffffffffff\n\
+                      CapEff:\t0000000000003000\nCapBnd:\t000001ffffffffff\nCapAmb:\t0000000000000000\n\
+                      NoNewPrivs:\t1\nSeccomp:\t2\nSeccomp_filters:\t1\n";
+        let security = Security::parse_status(status).unwrap();
+        assert_eq!(security.cap_effective, 0x3000);
+        assert_eq!(security.cap_permitted, 0x1ffffffffff);
+        assert_eq!(security.cap_bounding, 0x1ffffffffff);
+        assert!(security.no_new_privs);
+        assert_eq!(security.seccomp, "filter");
+        assert_eq!(security.effective_names(), vec!["net_admin", "net_raw"]);
+        assert!(Security::parse_status("Name:\tsshd\n").is_none());
+    }
+
+    #[test]
+    fn test_capability_names() {
+        assert!(capability_names(0).is_empty());
+        assert_eq!(capability_names(1 << 21), vec!["sys_admin"]);
+        assert_eq!(capability_names(0x1ffffffffff).len(), 41);
+    }
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(
+            parse_label(b"system_u:system_r:sshd_t:s0\0"),
+            Some("system_u:system_r:sshd_t:s0".to_string())
+        );
+        assert_eq!(
+            parse_label(b"docker-default (enforce)\n"),
+            Some("docker-default (enforce)".to_string())
+        );
+        assert_eq!(parse_label(b""), None);
+    }
+}
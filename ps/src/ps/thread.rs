@@ -0,0 +1,123 @@
+use crate::ps::error::Error;
+use crate::ps::proc::{parse_stat, pids, System, PROC_PATH};
+use chrono::Local;
+use std::env::consts;
+use std::fs;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Thread {
+    pub pid: u32,               // Process ID, the thread group leader
+    pub tid: u32,               // Thread ID
+    pub name: String,           // Thread name
+    pub state: String,          // Thread state
+    pub utime: u64,             // Cumulative user CPU time in milliseconds
+    pub stime: u64,             // Cumulative system CPU time in milliseconds
+    pub processor: Option<u32>, // CPU last executed on
+    pub lstart: i64,            // Process start time, same as `Process.lstart`
+    pub started_at: i64,        // Thread start time
+    pub created_at: i64,        // Timestamp collection
+}
+
+// Reads the threads of every process from /proc/<pid>/task (Linux only).
+pub fn threads() -> Result<Vec<Thread>, Error> {
+    if consts::OS != "linux" {
+        return Err(Error::Unimplemented {
+            os: consts::OS.to_string(),
+            arch: consts::ARCH.to_string(),
+        });
+    }
+    let system = System::read()?;
+    let created_at = Local::now().timestamp_millis();
+    let mut threads: Vec<Thread> = vec![];
+    for pid in pids()? {
+        match read_threads(pid, &system, created_at) {
+            Ok(process_threads) => threads.extend(process_threads),
+            // the process exited between the listing and the read
+            Err(Error::IO(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(threads)
+}
+
+fn read_threads(pid: u32, system: &System, created_at: i64) -> Result<Vec<Thread>, Error> {
+    let leader = parse_stat(&fs::read_to_string(format!("{PROC_PATH}/{pid}/stat"))?)?;
+    let lstart = system.start_time(&leader);
+    let mut threads: Vec<Thread> = vec![];
+    for entry in fs::read_dir(format!("{PROC_PATH}/{pid}/task"))? {
+        let Some(tid) = entry?.file_name().to_string_lossy().parse::<u32>().ok() else {
+            continue;
+        };
+        // a thread may exit while the others are read
+        let Ok(content) = fs::read_to_string(format!("{PROC_PATH}/{pid}/task/{tid}/stat")) else {
+            continue;
+        };
+        threads.push(parse_thread(&content, pid, lstart, system, created_at)?);
+    }
+    Ok(threads)
+}
+
+fn parse_thread(
+    content: &str,
+    pid: u32,
+    lstart: i64,
+    system: &System,
+    created_at: i64,
+) -> Result<Thread, Error> {
+    let stat = parse_stat(content)?;
+    Ok(Thread {
+        pid,
+        tid: stat.pid,
+        name: stat.comm.clone(),
+        state: stat.state.to_string(),
+        utime: system.ticks_to_millis(stat.utime),
+        stime: system.ticks_to_millis(stat.stime),
+        processor: stat.processor,
+        lstart,
+        started_at: system.start_time(&stat),
+        created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threads() {
+        if consts::OS == "linux" {
+            let threads = threads().unwrap();
+            let pid = std::process::id();
+            assert!(threads
+                .iter()
+                .any(|thread| thread.pid == pid && thread.tid == pid));
+        } else {
+            assert!(threads().is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_thread() {
+        let system = System {
+            boot_time: 1_700_000_000,
+            uptime: 110.0,
+            memory_total: 8192,
+            clock_ticks: 100,
+            page_size: 4096,
+        };
+        let content =
+            "1240 (GC Thread#0) R 1 1234 1234 0 -1 4194560 1042 0 0 0 250 150 0 0 20 0 12 0 \
+        1200 11186176 1024 18446744073709551615 1 1 0 0 0 0 0 4096 134295555 0 0 0 -1 5 0 0 0 0 0";
+        let thread = parse_thread(content, 1234, 1_700_000_010, &system, 42).unwrap();
+        assert_eq!(thread.pid, 1234);
+        assert_eq!(thread.tid, 1240);
+        assert_eq!(thread.name, "GC Thread#0");
+        assert_eq!(thread.state, "R");
+        assert_eq!(thread.utime, 2500);
+        assert_eq!(thread.stime, 1500);
+        assert_eq!(thread.processor, Some(5));
+        assert_eq!(thread.lstart, 1_700_000_010);
+        assert_eq!(thread.started_at, 1_700_000_012);
+        assert_eq!(thread.created_at, 42);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::ps::Process;
+use std::collections::{HashMap, HashSet};
+
+// Parent and child links of a process snapshot.
+// A parent is only linked when it started before its child, so a reused ppid is never followed.
+pub struct ProcessTree {
+    processes: HashMap<u32, Process>,
+    children: HashMap<u32, Vec<u32>>,
+}
+
+impl ProcessTree {
+    pub fn new(processes: Vec<Process>) -> ProcessTree {
+        let processes: HashMap<u32, Process> = processes
+            .into_iter()
+            .map(|process| (process.pid, process))
+            .collect();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for process in processes.values() {
+            if Self::parent_of(&processes, process).is_some() {
+                children.entry(process.ppid).or_default().push(process.pid);
+            }
+        }
+        children.values_mut().for_each(|pids| pids.sort());
+        ProcessTree {
+            processes,
+            children,
+        }
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&Process> {
+        self.processes.get(&pid)
+    }
+
+    pub fn parent(&self, pid: u32) -> Option<&Process> {
+        Self::parent_of(&self.processes, self.processes.get(&pid)?)
+    }
+
+    pub fn children(&self, pid: u32) -> Vec<&Process> {
+        self.children
+            .get(&pid)
+            .map(|pids| pids.iter().filter_map(|pid| self.get(*pid)).collect())
+            .unwrap_or_default()
+    }
+
+    // From the parent up to the root
+    pub fn ancestors(&self, pid: u32) -> Vec<&Process> {
+        let mut ancestors: Vec<&Process> = vec![];
+        let mut current = pid;
+        while let Some(parent) = self.parent(current) {
+            if parent.pid == pid || ancestors.iter().any(|ancestor| ancestor.pid == parent.pid) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent.pid;
+        }
+        ancestors
+    }
+
+    // Breadth first, children before grandchildren
+    pub fn descendants(&self, pid: u32) -> Vec<&Process> {
+        let mut descendants: Vec<&Process> = self.children(pid);
+        // equal start times can link two processes as each other's parent
+        let mut seen: HashSet<u32> = descendants.iter().map(|process| process.pid).collect();
+        seen.insert(pid);
+        let mut index = 0;
+        while index < descendants.len() {
+            for child in self.children(descendants[index].pid) {
+                if seen.insert(child.pid) {
+                    descendants.push(child);
+                }
+            }
+            index += 1;
+        }
+        descendants
+    }
+
+    pub fn roots(&self) -> Vec<&Process> {
+        let mut roots: Vec<&Process> = self
+            .processes
+            .values()
+            .filter(|process| Self::parent_of(&self.processes, process).is_none())
+            .collect();
+        roots.sort_by_key(|process| process.pid);
+        roots
+    }
+
+    fn parent_of<'a>(
+        processes: &'a HashMap<u32, Process>,
+        process: &Process,
+    ) -> Option<&'a Process> {
+        processes
+            .get(&process.ppid)
+            .filter(|parent| parent.pid != process.pid && parent.lstart <= process.lstart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_process(pid: u32, ppid: u32, lstart: i64) -> Process {
+        Process {
+            pid,
+            ppid,
+            lstart,
+            status: "S".to_string(),
+            command: format!("command_{pid}"),
+            ..Default::default()
+        }
+    }
+
+    fn create_tree() -> ProcessTree {
+        ProcessTree::new(vec![
+            create_process(1, 0, 100),
+            create_process(10, 1, 110),
+            create_process(20, 10, 120),
+            create_process(21, 10, 121),
+            create_process(30, 20, 130),
+            // started before its parent, the ppid was reused
+            create_process(40, 21, 90),
+        ])
+    }
+
+    fn pids(processes: Vec<&Process>) -> Vec<u32> {
+        processes.iter().map(|process| process.pid).collect()
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let tree = create_tree();
+        assert_eq!(pids(tree.ancestors(30)), vec![20, 10, 1]);
+        assert_eq!(pids(tree.ancestors(1)), Vec::<u32>::new());
+        assert_eq!(pids(tree.ancestors(40)), Vec::<u32>::new());
+        assert_eq!(pids(tree.ancestors(999)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_descendants() {
+        let tree = create_tree();
+        assert_eq!(pids(tree.descendants(10)), vec![20, 21, 30]);
+        assert_eq!(pids(tree.descendants(1)), vec![10, 20, 21, 30]);
+        assert_eq!(pids(tree.descendants(30)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_descendants_cycle() {
+        // same start second and each other's ppid, as a reused pid in a non atomic snapshot
+        let tree = ProcessTree::new(vec![
+            create_process(50, 51, 200),
+            create_process(51, 50, 200),
+        ]);
+        assert_eq!(pids(tree.descendants(50)), vec![51]);
+        assert_eq!(pids(tree.descendants(51)), vec![50]);
+        assert_eq!(pids(tree.ancestors(50)), vec![51]);
+    }
+
+    #[test]
+    fn test_parent_and_roots() {
+        let tree = create_tree();
+        assert_eq!(tree.parent(20).unwrap().pid, 10);
+        assert!(tree.parent(40).is_none());
+        assert_eq!(pids(tree.children(10)), vec![20, 21]);
+        assert_eq!(pids(tree.roots()), vec![1, 40]);
+    }
+}
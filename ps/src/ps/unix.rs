@@ -1,32 +1,114 @@
 use crate::ps::error::Error;
-use crate::ps::{Process, Ps};
+use crate::ps::{cgroup, security, Process, Ps};
 use chrono::{Local, NaiveDateTime};
+use std::env::consts;
 use std::process::{Command, Output};
 
 pub struct Unix;
 
 impl Ps for Unix {
+    fn exec() -> Result<Vec<Process>, Error> {
+        let output = Self::os_command()?;
+        let mut processes = Self::parse_output(&String::from_utf8_lossy(&output.stdout))?;
+        if consts::OS == "linux" {
+            processes.iter_mut().for_each(|process| {
+                cgroup::attribute(process);
+                security::attribute(process);
+            });
+        }
+        Ok(processes)
+    }
+}
+
+impl Unix {
     fn os_command() -> Result<Output, Error> {
+        // `priority` matches the kernel value of /proc, macOS only knows `pri`
+        let priority = if consts::OS == "linux" {
+            "priority"
+        } else {
+            "pri"
+        };
         Ok(Command::new("ps")
-            .args(["-eo", "pid,ppid,uid,lstart,pcpu,pmem,stat,args"])
+            .args([
+                "-eo",
+                &format!(
+                    "pid,ppid,ruid,uid,rgid,gid,rss,vsz,ni,{priority},tty,lstart,pcpu,pmem,stat,args"
+                ),
+            ])
             .output()?)
     }
 
-    fn parse_row(row: &str) -> Result<Process, Error> {
+    fn parse_output(output: &str) -> Result<Vec<Process>, Error> {
+        // all rows of a snapshot share the same timestamp
+        let created_at = Local::now().timestamp_millis();
+        let mut processes: Vec<Process> = vec![];
+        for row in output.lines().skip(1) {
+            if let Ok(process) = Self::parse_row(row, created_at) {
+                processes.push(process)
+            } else {
+                return Err(Error::ParseProcess {
+                    process: row.to_string(),
+                });
+            }
+        }
+        Ok(processes)
+    }
+
+    fn parse_row(row: &str, created_at: i64) -> Result<Process, Error> {
         let chunks: Vec<&str> = row.split_whitespace().collect();
         Ok(Process {
             pid: chunks[0].parse()?,
             ppid: chunks[1].parse()?,
-            uid: chunks[2].parse()?,
-            lstart: Self::parse_date(&chunks[3..8])?,
-            pcpu: chunks[8].parse()?,
-            pmem: chunks[9].parse()?,
-            status: chunks[10].to_string(),
-            command: chunks[11..].join(" "),
-            created_at: Local::now().timestamp_millis(),
+            uid: Self::parse_id(chunks[2])?,
+            euid: Self::parse_id(chunks[3])?,
+            gid: Self::parse_id(chunks[4])?,
+            egid: Self::parse_id(chunks[5])?,
+            rss: chunks[6].parse::<u64>()? * 1024,
+            vsz: chunks[7].parse::<u64>()? * 1024,
+            utime: None,
+            stime: None,
+            threads: None,
+            nice: Self::parse_scheduling(chunks[8])?,
+            priority: Self::parse_scheduling(chunks[9])?,
+            tty: Self::parse_tty(chunks[10]),
+            exe: None,
+            read_bytes: None,
+            write_bytes: None,
+            cgroup: None,
+            mnt_ns: None,
+            net_ns: None,
+            security: None,
+            lstart: Self::parse_date(&chunks[11..16])?,
+            pcpu: chunks[16].parse()?,
+            pmem: chunks[17].parse()?,
+            status: chunks[18].to_string(),
+            command: chunks[19..].join(" "),
+            created_at,
         })
     }
 
+    fn parse_id(chunk: &str) -> Result<u32, Error> {
+        // macOS prints some ids as negative values, such as -2 for nobody
+        Ok(chunk.parse::<i64>()? as u32)
+    }
+
+    fn parse_scheduling(chunk: &str) -> Result<i32, Error> {
+        // real-time processes have no nice value
+        if chunk == "-" {
+            Ok(0)
+        } else {
+            Ok(chunk.parse()?)
+        }
+    }
+
+    fn parse_tty(chunk: &str) -> Option<String> {
+        if chunk.chars().all(|c| c == '?') {
+            None
+        } else {
+            Some(chunk.to_string())
+        }
+    }
+
     fn parse_date(date_chunks: &[&str]) -> Result<i64, Error> {
         let format = "%a %b %d %H:%M:%S %Y";
         Ok(
@@ -41,14 +123,13 @@ impl Ps for Unix {
 #[cfg(test)]
 mod tests {
     use crate::ps::unix::Unix;
-    use crate::ps::Ps;
 
     fn create_ps_output() -> String {
-        "PID  PPID   UID                          STARTED %CPU %MEM STAT COMMAND
-    1     0     0 Tue Aug 29 08:01:10 2023  0.1  0.3 Ss   /sbin/init
- 1234     1  1000 Tue Aug 29 09:05:12 2023  0.0  1.2 S    /usr/lib/xorg/Xorg :0 -seat seat0 -auth /run/lightdm/root/:0 -nolisten tcp vt7 -novtswitch
- 5678  1234  1000 Tue Aug 29 09:15:05 2023  0.2  0.5 R    /usr/bin/python3 /home/user/script.py
- 9101  5678  1000 Tue Aug 29 10:00:02 2023  0.0  0.1 S    /bin/bash
+        "PID  PPID  RUID   UID  RGID   GID   RSS    VSZ  NI PRI TT                        STARTED %CPU %MEM STAT COMMAND
+    1     0     0     0     0     0  9292  23660   0  20 ?        Tue Aug 29 08:01:10 2023  0.1  0.3 Ss   /sbin/init
+ 1234     1  1000  1000  1000  1000 81920 412300   0  20 tty7     Tue Aug 29 09:05:12 2023  0.0  1.2 S    /usr/lib/xorg/Xorg :0 -seat seat0 -auth /run/lightdm/root/:0 -nolisten tcp vt7 -novtswitch
+ 5678  1234 65534 65534 65534 65534 20480  35000  10  30 pts/0    Tue Aug 29 09:15:05 2023  0.2  0.5 R    /usr/bin/python3 /home/user/script.py
+ 9101  5678  1000     0  1000  1000  5120  10000   -  -2 pts/1    Tue Aug 29 10:00:02 2023  0.0  0.1 S    /bin/bash
 ".to_string()
     }
 
@@ -56,17 +137,45 @@ mod tests {
     fn test_parse_output() {
         let processes = Unix::parse_output(&create_ps_output()).unwrap();
         assert_eq!(processes.len(), 4);
+        assert!(processes
+            .iter()
+            .all(|process| process.created_at == processes[0].created_at));
         assert_eq!(processes.last().unwrap().pid, 9101);
+        assert_eq!(processes[2].uid, 65534);
+        assert_eq!(processes[3].uid, 1000);
+        assert_eq!(processes[3].euid, 0);
+        assert_eq!(processes[2].nice, 10);
+        assert_eq!(processes[3].nice, 0);
+        assert_eq!(processes[3].priority, -2);
+        assert_eq!(processes[0].tty, None);
+        assert_eq!(processes[2].tty, Some("pts/0".to_string()));
         assert_eq!(processes[1].command, "/usr/lib/xorg/Xorg :0 -seat seat0 -auth /run/lightdm/root/:0 -nolisten tcp vt7 -novtswitch")
     }
 
+    #[test]
+    fn test_parse_id() {
+        assert_eq!(Unix::parse_id("4294967294").unwrap(), 4294967294);
+        assert_eq!(Unix::parse_id("-2").unwrap(), 4294967294);
+        assert!(Unix::parse_id("root").is_err());
+    }
+
     #[test]
     fn test_parse_row() {
-        let row = "1234     1  1000 Tue Aug 29 09:05:12 2023  0.0  1.2 S    /usr/lib/xorg/Xorg :0 -seat seat0 -auth /run/lightdm/root/:0 -nolisten tcp vt7 -novtswitch";
-        let process = Unix::parse_row(row).unwrap();
+        let row = "1234     1  1000  1001   100   101 81920 412300  -5  15 tty7     Tue Aug 29 09:05:12 2023  0.0  1.2 S    /usr/lib/xorg/Xorg :0 -seat seat0 -auth /run/lightdm/root/:0 -nolisten tcp vt7 -novtswitch";
+        let process = Unix::parse_row(row, 1_700_000_000_000).unwrap();
         assert_eq!(process.pid, 1234);
         assert_eq!(process.ppid, 1);
         assert_eq!(process.uid, 1000);
+        assert_eq!(process.euid, 1001);
+        assert_eq!(process.gid, 100);
+        assert_eq!(process.egid, 101);
+        assert_eq!(process.rss, 83886080);
+        assert_eq!(process.vsz, 422195200);
+        assert_eq!(process.threads, None);
+        assert_eq!(process.nice, -5);
+        assert_eq!(process.priority, 15);
+        assert_eq!(process.tty, Some("tty7".to_string()));
+        assert_eq!(process.created_at, 1_700_000_000_000);
         assert_eq!(process.pcpu, 0.0);
         assert_eq!(process.pmem, 1.2);
         assert_eq!(process.status, "S");
@@ -1,8 +1,13 @@
 use crate::pipeline::error::Error;
 use config;
+use config::ConfigError;
+use lsof::lsof::scope::Scope;
+use network::options::CaptureOptions;
+use ps::ps::Source;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
-use tracing::Level;
+use tracing::{warn, Level};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 
 const CONFIG_FILE_PATH: &str = "rstracer.toml";
@@ -11,19 +16,186 @@ const CONFIG_FILE_PATH: &str = "rstracer.toml";
 pub struct Config {
     pub in_memory: bool,
     pub request: ChannelConfig,
-    pub ps: ChannelConfig,
+    pub ps: PsConfig,
+    pub process_event: ProcessEventConfig,
+    pub environ: EnvironConfig,
+    pub library: LibraryConfig,
+    pub executable: ExecutableConfig,
     pub lsof: LsofConfig,
-    pub network: ChannelConfig,
+    pub file_activity: FileActivityConfig,
+    pub network: NetworkConfig,
     pub vacuum: VacuumConfig,
     pub export: ExportConfig,
     pub schedule: ScheduleConfig,
     pub logger: LoggerConfig,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct PsConfig {
+    pub source: String,
+    pub thread: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+}
+
+impl PsConfig {
+    pub fn source(&self) -> Source {
+        match self.source.to_lowercase().as_str() {
+            "command" => Source::COMMAND,
+            "proc" => Source::PROC,
+            source => panic!("Unknown ps source '{source}'"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProcessEventConfig {
+    pub enabled: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EnvironConfig {
+    pub enabled: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+    pub allow: Vec<String>,
+    pub redact: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LibraryConfig {
+    pub enabled: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExecutableConfig {
+    pub enabled: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct LsofConfig {
-    pub regular: ChannelConfig,
+    pub source: String,
+    #[serde(default)]
+    pub scope: Vec<ScopeConfig>,
+    pub regular: Option<ChannelConfig>,
     pub network: ChannelConfig,
+    pub ipc: IpcConfig,
+}
+
+impl LsofConfig {
+    pub fn source(&self) -> lsof::lsof::Source {
+        match self.source.to_lowercase().as_str() {
+            "command" => lsof::lsof::Source::COMMAND,
+            "proc" => lsof::lsof::Source::PROC,
+            source => panic!("Unknown lsof source '{source}'"),
+        }
+    }
+
+    // `[lsof.regular]` of the previous versions is read as the default scope with its own frequency.
+    pub fn scopes(&self) -> Result<Vec<ScopeConfig>, Error> {
+        match (&self.regular, self.scope.is_empty()) {
+            (Some(_), false) => Err(ConfigError::Message(
+                "lsof.regular is replaced by [[lsof.scope]], remove it".to_string(),
+            )
+            .into()),
+            (Some(regular), true) => {
+                warn!("lsof.regular is deprecated, use a [[lsof.scope]] on \"/\" instead");
+                let scope = default_scope();
+                Ok(vec![ScopeConfig {
+                    channel: ChannelConfig {
+                        producer_frequency: regular
+                            .producer_frequency
+                            .or(scope.channel.producer_frequency),
+                        ..regular.clone()
+                    },
+                    ..scope
+                }])
+            }
+            (None, true) => Ok(vec![default_scope()]),
+            (None, false) => match self
+                .scope
+                .iter()
+                .find(|scope| scope.channel.producer_frequency.is_none())
+            {
+                Some(scope) => Err(ConfigError::Message(format!(
+                    "lsof.scope {} has no producer_frequency",
+                    scope.path
+                ))
+                .into()),
+                None => Ok(self.scope.clone()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScopeConfig {
+    pub path: String,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub deleted_only: bool,
+    #[serde(flatten)]
+    pub channel: ChannelConfig,
+}
+
+impl ScopeConfig {
+    pub fn scope(&self) -> Result<Scope, Error> {
+        Ok(Scope::new(
+            &self.path,
+            &self.include,
+            &self.exclude,
+            self.deleted_only,
+        )?)
+    }
+}
+
+// Without scope, the whole root filesystem is watched as `lsof /` does.
+fn default_scope() -> ScopeConfig {
+    ScopeConfig {
+        path: "/".to_string(),
+        include: vec![],
+        exclude: vec![],
+        deleted_only: false,
+        channel: ChannelConfig {
+            channel_size: None,
+            producer_frequency: Some(20000),
+            consumer_batch_size: 200,
+        },
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IpcConfig {
+    pub enabled: bool,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+}
+
+impl IpcConfig {
+    pub fn channel(&self) -> ChannelConfig {
+        ChannelConfig {
+            channel_size: None,
+            producer_frequency: Some(self.producer_frequency),
+            consumer_batch_size: self.consumer_batch_size,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FileActivityConfig {
+    pub enabled: bool,
+    pub directories: Vec<String>,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -33,6 +205,60 @@ pub struct ChannelConfig {
     pub consumer_batch_size: usize,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct NetworkConfig {
+    pub channel_size: usize,
+    pub producer_frequency: u64,
+    pub consumer_batch_size: usize,
+    pub stats_frequency: u64,
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    #[serde(default)]
+    pub replay: Vec<String>,
+}
+
+impl NetworkConfig {
+    pub fn channel(&self) -> ChannelConfig {
+        ChannelConfig {
+            channel_size: Some(self.channel_size),
+            producer_frequency: Some(self.producer_frequency),
+            consumer_batch_size: self.consumer_batch_size,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CaptureConfig {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub filter: Option<String>,
+    pub filters: HashMap<String, String>,
+    pub snaplen: Option<i32>,
+    pub promiscuous: bool,
+    pub immediate: bool,
+    pub buffer_size: Option<i32>,
+    pub nanosecond: bool,
+}
+
+impl CaptureConfig {
+    pub fn options(&self) -> Result<CaptureOptions, Error> {
+        Ok(CaptureOptions {
+            snaplen: self.snaplen,
+            promiscuous: self.promiscuous,
+            immediate: self.immediate,
+            buffer_size: self.buffer_size,
+            nanosecond: self.nanosecond,
+            ..CaptureOptions::new(
+                &self.include,
+                &self.exclude,
+                self.filter.clone(),
+                self.filters.clone(),
+            )?
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct VacuumConfig {
     pub bronze: u64,
@@ -101,18 +327,70 @@ pub fn read_config() -> Result<Config, Error> {
         .set_default("request.channel_size", 100)?
         .set_default("request.consumer_batch_size", 20)?
         // ps
+        .set_default("ps.source", "command")?
+        .set_default("ps.thread", false)?
         .set_default("ps.producer_frequency", 3000)?
         .set_default("ps.consumer_batch_size", 200)?
-        // lsof regular
-        .set_default("lsof.regular.producer_frequency", 20000)?
-        .set_default("lsof.regular.consumer_batch_size", 200)?
+        // process event
+        .set_default("process_event.enabled", false)?
+        .set_default("process_event.producer_frequency", 1000)?
+        .set_default("process_event.consumer_batch_size", 200)?
+        // environ
+        .set_default("environ.enabled", false)?
+        .set_default("environ.producer_frequency", 10000)?
+        .set_default("environ.consumer_batch_size", 200)?
+        .set_default(
+            "environ.allow",
+            vec![
+                "PATH",
+                "LANG",
+                "TZ",
+                "HOME",
+                "USER",
+                "SHELL",
+                "PWD",
+                "(?i)(http|https|no|all)_proxy",
+                "LD_PRELOAD",
+                "LD_LIBRARY_PATH",
+                "JAVA_OPTS",
+                "JAVA_TOOL_OPTIONS",
+            ],
+        )?
+        .set_default(
+            "environ.redact",
+            vec![
+                r"[^/@\s]+@",
+                r"(?i)(password|passwd|secret|token|api_?key)=\S*",
+            ],
+        )?
+        // library
+        .set_default("library.enabled", false)?
+        .set_default("library.producer_frequency", 60000)?
+        .set_default("library.consumer_batch_size", 200)?
+        // executable
+        .set_default("executable.enabled", false)?
+        .set_default("executable.producer_frequency", 10000)?
+        .set_default("executable.consumer_batch_size", 200)?
+        // lsof
+        .set_default("lsof.source", "command")?
         // lsof network
         .set_default("lsof.network.producer_frequency", 3000)?
         .set_default("lsof.network.consumer_batch_size", 200)?
+        // lsof ipc
+        .set_default("lsof.ipc.enabled", false)?
+        .set_default("lsof.ipc.producer_frequency", 10000)?
+        .set_default("lsof.ipc.consumer_batch_size", 200)?
+        // file activity
+        .set_default("file_activity.enabled", false)?
+        .set_default("file_activity.directories", vec!["/etc"])?
+        .set_default("file_activity.producer_frequency", 1000)?
+        .set_default("file_activity.consumer_batch_size", 200)?
         // network
         .set_default("network.channel_size", 500)?
         .set_default("network.producer_frequency", 1000)?
         .set_default("network.consumer_batch_size", 200)?
+        .set_default("network.stats_frequency", 10000)?
+        .set_default("network.capture.exclude", vec!["veth*", "docker*", "br-*"])?
         // export
         .set_default("export.directory", "export/")?
         .set_default("export.format", "parquet")?
@@ -3,11 +3,16 @@ use crate::pipeline::database::{close_connection, execute_request};
 use crate::pipeline::error::Error;
 use crate::pipeline::stage::schema::create_schema_request;
 use crate::pipeline::{
-    execute_final_schedule_request, execute_request_task, execute_schedule_request_task,
-    network_capture_sink_task, open_file_task, process_task,
+    environ_task, executable_task, execute_final_schedule_request, execute_request_task,
+    execute_schedule_request_task, file_activity_task, library_task, network_capture_sink_task,
+    network_capture_stats_task, open_file_task, optional_open_file_task, process_event_task,
+    process_task,
 };
 use lsof::lsof::FileType;
 use network::capture::Capture;
+use network::options::CaptureOptions;
+use network::stats::StatsRegistry;
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::join;
@@ -22,47 +27,126 @@ pub async fn run(stop_flag: Arc<AtomicBool>) -> Result<(), Error> {
     let config = read_config()?;
     subscribe_logger(&config.logger);
     execute_request(&create_schema_request(), config.in_memory)?;
+    // invalid patterns are rejected before any task starts
+    let scopes = config
+        .lsof
+        .scopes()?
+        .iter()
+        .map(|scope| Ok((scope.channel.clone(), scope.scope()?)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let capture_options = Arc::new(config.network.capture.options()?);
+    let capture_stats = Arc::new(StatsRegistry::default());
 
     let (sender_request, receiver_request): (Sender<String>, Receiver<String>) =
         channel(config.request.channel_size.unwrap());
-    let (sender_capture, receiver_capture): (Sender<Capture>, Receiver<Capture>) =
-        channel(config.network.channel_size.unwrap());
+    let (sender_capture, receiver_capture): (Sender<Vec<Capture>>, Receiver<Vec<Capture>>) =
+        channel(config.network.channel_size);
 
     let execute_schedule_request_task = start_schedule_request_task(&config, &stop_flag);
     let execute_request_task = start_execute_request_task(&config, receiver_request, &stop_flag);
     let process_task = start_process_task(&config, &sender_request, &stop_flag);
-    let open_file_regular_task = start_open_file_task(
-        &config.lsof.regular,
-        FileType::REGULAR,
-        &sender_request,
+    let process_event_task = start_process_event_task(&config, &sender_request, &stop_flag);
+    let environ_task = start_task(
+        environ_task(
+            config.environ.clone(),
+            sender_request.clone(),
+            stop_flag.clone(),
+        ),
         &stop_flag,
     );
-    let open_file_network_task = start_open_file_task(
-        &config.lsof.network,
-        FileType::NETWORK,
-        &sender_request,
+    let library_task = start_task(
+        library_task(
+            config.library.clone(),
+            sender_request.clone(),
+            stop_flag.clone(),
+        ),
+        &stop_flag,
+    );
+    let executable_task = start_task(
+        executable_task(
+            config.executable.clone(),
+            sender_request.clone(),
+            stop_flag.clone(),
+        ),
+        &stop_flag,
+    );
+    let open_file_scope_tasks: Vec<JoinHandle<()>> = scopes
+        .into_iter()
+        .map(|(channel, scope)| {
+            start_task(
+                open_file_task(
+                    channel,
+                    config.lsof.source(),
+                    FileType::SCOPE(scope),
+                    sender_request.clone(),
+                    stop_flag.clone(),
+                ),
+                &stop_flag,
+            )
+        })
+        .collect();
+    let open_file_network_task = start_task(
+        open_file_task(
+            config.lsof.network.clone(),
+            config.lsof.source(),
+            FileType::NETWORK,
+            sender_request.clone(),
+            stop_flag.clone(),
+        ),
+        &stop_flag,
+    );
+    let ipc_task = start_task(
+        optional_open_file_task(
+            config.lsof.ipc.enabled,
+            config.lsof.ipc.channel(),
+            config.lsof.source(),
+            FileType::IPC,
+            sender_request.clone(),
+            stop_flag.clone(),
+        ),
+        &stop_flag,
+    );
+    let file_activity_task = start_file_activity_task(&config, &sender_request, &stop_flag);
+    let network_capture_source_task = start_network_capture_source_task(
+        &config,
+        capture_options,
+        Arc::clone(&capture_stats),
+        sender_capture,
         &stop_flag,
     );
-    let network_capture_source_task = start_network_capture_source_task(sender_capture, &stop_flag);
     let network_capture_sink_task =
         start_network_capture_sink_task(&config, receiver_capture, &sender_request, &stop_flag);
+    let network_capture_stats_task =
+        start_network_capture_stats_task(&config, capture_stats, &sender_request, &stop_flag);
 
     let (
         execute_schedule_request_result,
         execute_request_result,
         process_result,
-        open_file_regular_result,
+        process_event_result,
+        environ_result,
+        library_result,
+        executable_result,
         open_file_network_result,
+        ipc_result,
+        file_activity_result,
         network_capture_source_result,
         network_capture_sink_result,
+        network_capture_stats_result,
     ) = join!(
         execute_schedule_request_task,
         execute_request_task,
         process_task,
-        open_file_regular_task,
+        process_event_task,
+        environ_task,
+        library_task,
+        executable_task,
         open_file_network_task,
+        ipc_task,
+        file_activity_task,
         network_capture_source_task,
         network_capture_sink_task,
+        network_capture_stats_task,
     );
 
     execute_final_schedule_request(&config)?;
@@ -71,10 +155,19 @@ pub async fn run(stop_flag: Arc<AtomicBool>) -> Result<(), Error> {
     execute_schedule_request_result?;
     execute_request_result?;
     process_result?;
-    open_file_regular_result?;
+    process_event_result?;
+    environ_result?;
+    library_result?;
+    executable_result?;
+    for open_file_scope_task in open_file_scope_tasks {
+        open_file_scope_task.await?;
+    }
     open_file_network_result?;
+    ipc_result?;
+    file_activity_result?;
     network_capture_source_result?;
     network_capture_sink_result?;
+    network_capture_stats_result?;
 
     Ok(())
 }
@@ -130,33 +223,83 @@ fn start_process_task(
     })
 }
 
-fn start_open_file_task(
-    config: &config::ChannelConfig,
-    file_type: FileType,
+fn start_process_event_task(
+    config: &config::Config,
     sender_request: &Sender<String>,
     stop_flag: &Arc<AtomicBool>,
 ) -> JoinHandle<()> {
-    let config_clone = config.clone();
+    let config_clone = config.process_event.clone();
+    let sender_clone = sender_request.clone();
+    let stop_flag_read = stop_flag.clone();
+    let stop_flag_write = stop_flag.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = process_event_task(&config_clone, sender_clone, stop_flag_read) {
+            stop_flag_write.store(true, Ordering::Release);
+            error!("{}", e);
+        }
+    })
+}
+
+fn start_file_activity_task(
+    config: &config::Config,
+    sender_request: &Sender<String>,
+    stop_flag: &Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    let config_clone = config.file_activity.clone();
     let sender_clone = sender_request.clone();
     let stop_flag_read = stop_flag.clone();
+    let stop_flag_write = stop_flag.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = file_activity_task(&config_clone, sender_clone, stop_flag_read) {
+            stop_flag_write.store(true, Ordering::Release);
+            error!("{}", e);
+        }
+    })
+}
+
+// A failing task stops the others.
+fn start_task<F>(task: F, stop_flag: &Arc<AtomicBool>) -> JoinHandle<()>
+where
+    F: Future<Output = Result<(), Error>> + Send + 'static,
+{
     let stop_flag_write = stop_flag.clone();
     tokio::spawn(async move {
-        if let Err(e) = open_file_task(&config_clone, file_type, sender_clone, stop_flag_read).await
-        {
+        if let Err(e) = task.await {
             stop_flag_write.store(true, Ordering::Release);
             error!("{}", e);
         }
     })
 }
 
+// Capture files replace the live interfaces when configured.
 fn start_network_capture_source_task(
-    sender_capture: Sender<Capture>,
+    config: &config::Config,
+    capture_options: Arc<CaptureOptions>,
+    capture_stats: Arc<StatsRegistry>,
+    sender_capture: Sender<Vec<Capture>>,
     stop_flag: &Arc<AtomicBool>,
 ) -> JoinHandle<()> {
+    let replay = config.network.replay.clone();
     let stop_flag_read = stop_flag.clone();
     let stop_flag_write = stop_flag.clone();
     tokio::spawn(async move {
-        if let Err(e) = network::producer(sender_capture, &stop_flag_read).await {
+        let result = if replay.is_empty() {
+            network::producer(
+                sender_capture,
+                capture_options,
+                capture_stats,
+                &stop_flag_read,
+            )
+            .await
+        } else {
+            let stop_flag = stop_flag_read.clone();
+            tokio::task::spawn_blocking(move || {
+                network::file::replay(&replay, sender_capture, stop_flag)
+            })
+            .await
+            .unwrap_or_else(|e| Err(e.into()))
+        };
+        if let Err(e) = result {
             stop_flag_write.store(true, Ordering::Release);
             error!("{}", e);
         }
@@ -165,11 +308,11 @@ fn start_network_capture_source_task(
 
 fn start_network_capture_sink_task(
     config: &config::Config,
-    receiver: Receiver<Capture>,
+    receiver: Receiver<Vec<Capture>>,
     sender_request: &Sender<String>,
     stop_flag: &Arc<AtomicBool>,
 ) -> JoinHandle<()> {
-    let config_clone = config.network.clone();
+    let config_clone = config.network.channel();
     let sender_clone = sender_request.clone();
     let stop_flag_read = stop_flag.clone();
     let stop_flag_write = stop_flag.clone();
@@ -182,3 +325,23 @@ fn start_network_capture_sink_task(
         }
     })
 }
+
+fn start_network_capture_stats_task(
+    config: &config::Config,
+    capture_stats: Arc<StatsRegistry>,
+    sender_request: &Sender<String>,
+    stop_flag: &Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    let frequency = config.network.stats_frequency;
+    let sender_clone = sender_request.clone();
+    let stop_flag_read = stop_flag.clone();
+    let stop_flag_write = stop_flag.clone();
+    tokio::spawn(async move {
+        if let Err(e) =
+            network_capture_stats_task(frequency, capture_stats, sender_clone, stop_flag_read).await
+        {
+            stop_flag_write.store(true, Ordering::Release);
+            error!("{}", e);
+        }
+    })
+}
@@ -17,6 +17,10 @@ pub enum Error {
     Ps(#[from] ps::ps::error::Error),
     #[error("Lsof error: {0}")]
     Lsof(#[from] lsof::lsof::error::Error),
+    #[error("Network error: {0}")]
+    Network(#[from] network::error::Error),
     #[error("Etc error: {0}")]
     Etc(#[from] etc::etc::error::Error),
+    #[error("Regex error: {0}")]
+    Regex(#[from] regex::Error),
 }
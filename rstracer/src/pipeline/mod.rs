@@ -1,22 +1,38 @@
-use crate::config::{ChannelConfig, Config};
+use crate::config::{
+    ChannelConfig, Config, EnvironConfig, ExecutableConfig, FileActivityConfig, LibraryConfig,
+    ProcessEventConfig, PsConfig,
+};
 use crate::pipeline::database::execute_request;
 use crate::pipeline::error::Error;
-use crate::pipeline::stage::bronze::{concat_requests, create_insert_batch_request, Bronze};
-use crate::pipeline::stage::{export, file, gold, silver, vacuum};
+use crate::pipeline::redaction::Redaction;
+use crate::pipeline::stage::bronze::{
+    concat_requests, create_insert_batch_request, device_addresses_to_sql, Bronze, BronzeBatch,
+};
+use crate::pipeline::stage::{export, file, gold, silver, tech, vacuum};
 use chrono::Local;
-use lsof::lsof::{lsof, FileType, OpenFile};
+use lsof::lsof::activity::{ActivityWatcher, FileActivity};
+use lsof::lsof::library::libraries;
+use lsof::lsof::{lsof, FileType, Source};
 use network::capture::Capture;
-use ps::ps::{ps, Process};
+use network::stats::StatsRegistry;
+use pcap::Device;
+use ps::ps::environ::EnvironReader;
+use ps::ps::event::{EventListener, ProcessEvent};
+use ps::ps::executable::ExecutableHasher;
+use ps::ps::thread::{threads, Thread};
+use ps::ps::{ps, Process, Source as ProcessSource};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::task::spawn_blocking;
 use tokio::time::{sleep, timeout};
 use tracing::{info, warn};
 
 pub mod database;
 pub mod error;
+pub mod redaction;
 pub mod stage;
 
 const TIMEOUT_MS: u64 = 1000;
@@ -76,7 +92,11 @@ fn get_schedule_request_task(config: &Config) -> Result<HashMap<(&str, String, u
         Local::now().timestamp(),
     );
     tasks.insert(
-        ("gold", gold::request(), config.schedule.gold),
+        (
+            "gold",
+            gold::request(!config.network.replay.is_empty()),
+            config.schedule.gold,
+        ),
         Local::now().timestamp() + 1,
     );
     tasks.insert(
@@ -104,7 +124,10 @@ fn get_schedule_request_task(config: &Config) -> Result<HashMap<(&str, String, u
 pub fn execute_final_schedule_request(config: &Config) -> Result<(), Error> {
     execute_request(&silver::request(), config.in_memory)?;
     info!("final silver request executed");
-    execute_request(&gold::request(), config.in_memory)?;
+    execute_request(
+        &gold::request(!config.network.replay.is_empty()),
+        config.in_memory,
+    )?;
     info!("final gold request executed");
     if config.schedule.export > 0 {
         execute_request(&export::request(&config.export), config.in_memory)?;
@@ -142,15 +165,23 @@ pub async fn execute_schedule_request_task(
 }
 
 pub async fn process_task(
-    config: &ChannelConfig,
+    config: &PsConfig,
     sender_request: Sender<String>,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<(), Error> {
-    let frequency = config.producer_frequency.unwrap();
+    let frequency = config.producer_frequency;
+    let source = config.source();
+    let thread = config.thread && source == ProcessSource::PROC;
+    if config.thread && !thread {
+        warn!(
+            "thread collection reads /proc, disabled with the {} source",
+            source
+        );
+    }
 
     while !stop_flag.load(Ordering::Relaxed) {
         let start = Local::now().timestamp_millis();
-        let processes = ps()?;
+        let processes = ps(&source)?;
         let length = processes.len();
 
         let batches: Vec<Vec<Process>> = processes
@@ -168,6 +199,26 @@ pub async fn process_task(
             }
         }
 
+        if thread {
+            let threads = threads()?;
+            info!("collected {} threads", threads.len());
+
+            let batches: Vec<Vec<Thread>> = threads
+                .chunks(config.consumer_batch_size)
+                .map(|chunk| chunk.to_vec())
+                .collect();
+
+            for batch in batches {
+                if let Err(e) = sender_request
+                    .send(create_insert_batch_request(batch))
+                    .await
+                {
+                    warn!("{}", e);
+                    stop_flag.store(true, Ordering::Release);
+                }
+            }
+        }
+
         let duration = Local::now().timestamp_millis() - start;
 
         if duration > frequency as i64 {
@@ -189,27 +240,119 @@ pub async fn process_task(
     Ok(())
 }
 
-pub async fn open_file_task(
-    config: &ChannelConfig,
-    file_type: FileType,
+// Reads the kernel events on a blocking thread, the listener waits at most the producer frequency.
+pub fn process_event_task(
+    config: &ProcessEventConfig,
     sender_request: Sender<String>,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<(), Error> {
-    let frequency = config.producer_frequency.unwrap();
+    if !config.enabled {
+        info!("process event producer disabled");
+        return Ok(());
+    }
+
+    let frequency = config.producer_frequency;
+    let mut listener = EventListener::new(Duration::from_millis(frequency))?;
+    let mut events: Vec<ProcessEvent> = Vec::with_capacity(config.consumer_batch_size);
+    let mut sent_at = Local::now().timestamp_millis();
 
     while !stop_flag.load(Ordering::Relaxed) {
-        let start = Local::now().timestamp_millis();
-        let open_files = lsof(&file_type)?;
-        let length = open_files.len();
+        events.extend(listener.receive()?);
+        let now = Local::now().timestamp_millis();
+
+        if events.len() >= config.consumer_batch_size || now - sent_at > frequency as i64 {
+            let length = events.len();
+            for batch in events.chunks(config.consumer_batch_size) {
+                if let Err(e) =
+                    sender_request.blocking_send(create_insert_batch_request(batch.to_vec()))
+                {
+                    warn!("{}", e);
+                    stop_flag.store(true, Ordering::Release);
+                }
+            }
+            events.clear();
+            sent_at = now;
+            info!("sent bronze sql request with {} process events", length);
+        }
+    }
 
-        let batches: Vec<Vec<OpenFile>> = open_files
-            .chunks(config.consumer_batch_size)
-            .map(|chunk| chunk.to_vec())
-            .collect();
+    info!("process event producer stop gracefully");
 
-        for batch in batches {
+    Ok(())
+}
+
+pub fn file_activity_task(
+    config: &FileActivityConfig,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    if !config.enabled {
+        info!("file activity producer disabled");
+        return Ok(());
+    }
+
+    let frequency = config.producer_frequency;
+    let mut watcher = ActivityWatcher::new(&config.directories, Duration::from_millis(frequency))?;
+    let mut activities: Vec<FileActivity> = Vec::with_capacity(config.consumer_batch_size);
+    let mut sent_at = Local::now().timestamp_millis();
+    let mut overflows = 0;
+
+    while !stop_flag.load(Ordering::Relaxed) {
+        activities.extend(watcher.receive()?);
+        if watcher.overflows() > overflows {
+            overflows = watcher.overflows();
+            warn!(
+                "inotify queue overflowed, file activities were lost. {} overflows so far",
+                overflows
+            );
+        }
+        let now = Local::now().timestamp_millis();
+
+        if activities.len() >= config.consumer_batch_size || now - sent_at > frequency as i64 {
+            let length = activities.len();
+            for batch in activities.chunks(config.consumer_batch_size) {
+                if let Err(e) =
+                    sender_request.blocking_send(create_insert_batch_request(batch.to_vec()))
+                {
+                    warn!("{}", e);
+                    stop_flag.store(true, Ordering::Release);
+                }
+            }
+            activities.clear();
+            sent_at = now;
+            info!("sent bronze sql request with {} file activities", length);
+        }
+    }
+
+    info!("file activity producer stop gracefully");
+
+    Ok(())
+}
+
+// Sends the rows returned by `read` in batches, then waits for the rest of the frequency. `read` runs on a
+// blocking thread as it walks /proc or hashes files.
+async fn periodic_task<T, F>(
+    name: &str,
+    frequency: u64,
+    batch_size: usize,
+    mut read: F,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error>
+where
+    T: BronzeBatch + Clone + Send + 'static,
+    F: FnMut() -> Result<Vec<T>, Error> + Send + 'static,
+{
+    while !stop_flag.load(Ordering::Relaxed) {
+        let start = Local::now().timestamp_millis();
+        let (rows, returned) = spawn_blocking(move || (read(), read)).await?;
+        read = returned;
+        let rows = rows?;
+        let length = rows.len();
+
+        for batch in rows.chunks(batch_size) {
             if let Err(e) = sender_request
-                .send(create_insert_batch_request(batch))
+                .send(create_insert_batch_request(batch.to_vec()))
                 .await
             {
                 warn!("{}", e);
@@ -221,74 +364,213 @@ pub async fn open_file_task(
 
         if duration > frequency as i64 {
             warn!(
-                "sending rows is longer than the frequency. {} bronze {} open files sent in {} ms",
-                length, &file_type, duration
+                "sending rows is longer than the frequency. {} bronze {} sent in {} ms",
+                length, name, duration
             );
         } else {
             info!(
-                "sent bronze sql request with {} {} open files in {} ms",
-                length, &file_type, duration
+                "sent bronze sql request with {} {} in {} ms",
+                length, name, duration
             );
             sleep(Duration::from_millis(frequency - duration as u64)).await;
         }
     }
 
-    info!("open file producer stop gracefully");
+    info!("{} producer stop gracefully", name);
 
     Ok(())
 }
 
+// Only the environment of the new processes is read, the variables are filtered before leaving the task.
+pub async fn environ_task(
+    config: EnvironConfig,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    if !config.enabled {
+        info!("environ producer disabled");
+        return Ok(());
+    }
+
+    let redaction = Redaction::new(&config)?;
+    let mut reader = EnvironReader::new()?;
+    periodic_task(
+        "environment variables",
+        config.producer_frequency,
+        config.consumer_batch_size,
+        move || Ok(redaction.apply(reader.read_new()?)),
+        sender_request,
+        stop_flag,
+    )
+    .await
+}
+
+// Hashing is cached per file, only the new or changed executables are sent.
+pub async fn executable_task(
+    config: ExecutableConfig,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    if !config.enabled {
+        info!("executable producer disabled");
+        return Ok(());
+    }
+
+    let mut hasher = ExecutableHasher::new()?;
+    periodic_task(
+        "executables",
+        config.producer_frequency,
+        config.consumer_batch_size,
+        move || Ok(hasher.read_new()?),
+        sender_request,
+        stop_flag,
+    )
+    .await
+}
+
+pub async fn library_task(
+    config: LibraryConfig,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    if !config.enabled {
+        info!("library producer disabled");
+        return Ok(());
+    }
+
+    periodic_task(
+        "libraries",
+        config.producer_frequency,
+        config.consumer_batch_size,
+        || Ok(libraries()?),
+        sender_request,
+        stop_flag,
+    )
+    .await
+}
+
+pub async fn open_file_task(
+    config: ChannelConfig,
+    source: Source,
+    file_type: FileType,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    let name = format!("{} open files", file_type);
+    periodic_task(
+        &name,
+        config.producer_frequency.unwrap(),
+        config.consumer_batch_size,
+        move || Ok(lsof(&source, &file_type)?),
+        sender_request,
+        stop_flag,
+    )
+    .await
+}
+
+// The optional open file types, such as the IPC endpoints, have their own switch.
+pub async fn optional_open_file_task(
+    enabled: bool,
+    config: ChannelConfig,
+    source: Source,
+    file_type: FileType,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    if !enabled {
+        info!("{} open file producer disabled", file_type);
+        return Ok(());
+    }
+
+    open_file_task(config, source, file_type, sender_request, stop_flag).await
+}
+
+pub async fn network_capture_stats_task(
+    frequency: u64,
+    registry: Arc<StatsRegistry>,
+    sender_request: Sender<String>,
+    stop_flag: Arc<AtomicBool>,
+) -> Result<(), Error> {
+    while !stop_flag.load(Ordering::Relaxed) {
+        let stats = registry.snapshot();
+        let length = stats.len();
+        if length > 0 {
+            if let Err(e) = sender_request
+                .send(tech::capture_stats_request(&stats))
+                .await
+            {
+                warn!("{}", e);
+                stop_flag.store(true, Ordering::Release);
+            } else {
+                info!("sent capture stats of {} interfaces", length);
+            }
+        }
+        sleep(Duration::from_millis(frequency)).await;
+    }
+
+    info!("network capture stats producer stop gracefully");
+
+    Ok(())
+}
+
+// Every batch waiting in the channel is read at once, the sink only pauses once it is drained.
 pub async fn network_capture_sink_task(
     config: &ChannelConfig,
-    receiver_capture: Receiver<Capture>,
+    receiver_capture: Receiver<Vec<Capture>>,
     sender_request: Sender<String>,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<(), Error> {
     let mut receiver_capture = receiver_capture;
 
     while !stop_flag.load(Ordering::Relaxed) {
-        let mut capture_buffer: Vec<Capture> = Vec::with_capacity(config.consumer_batch_size);
+        let mut batch_buffer: Vec<Vec<Capture>> = Vec::new();
+        let limit = receiver_capture.max_capacity();
 
         info!(
-            "network capture receiver contains {} elements",
+            "network capture receiver contains {} batches",
             receiver_capture.len()
         );
 
         match timeout(
             Duration::from_millis(TIMEOUT_MS),
-            receiver_capture.recv_many(&mut capture_buffer, config.consumer_batch_size),
+            receiver_capture.recv_many(&mut batch_buffer, limit),
         )
         .await
         {
             Ok(_) => {
-                let length = capture_buffer.len();
-                info!(
-                    "network capture batch read {} / {}",
-                    length, config.consumer_batch_size
-                );
                 let start = Local::now().timestamp_millis();
-
-                let values: Vec<String> = capture_buffer
-                    .iter()
-                    .map(|file| file.to_insert_sql(None))
-                    .collect();
-
-                let request = concat_requests(values, config.consumer_batch_size).join(";");
-
-                if let Err(e) = sender_request.send(request).await {
-                    warn!("{}", e);
-                    stop_flag.store(true, Ordering::Release);
-                } else {
-                    let duration = Local::now().timestamp_millis() - start;
-                    info!("sent {} network capture sql in {} ms", length, duration);
+                let captures: Vec<&Capture> = batch_buffer.iter().flatten().collect();
+                let length = captures.len();
+                info!("network capture batch read {}", length);
+
+                for chunk in captures.chunks(config.consumer_batch_size) {
+                    let mut values: Vec<String> = chunk
+                        .iter()
+                        .map(|capture| capture.to_insert_sql(None))
+                        .collect();
+                    let devices: HashMap<&str, &Device> = chunk
+                        .iter()
+                        .map(|capture| (capture.device.name.as_str(), capture.device.as_ref()))
+                        .collect();
+                    values.extend(devices.values().copied().map(device_addresses_to_sql));
+                    let request = concat_requests(values, config.consumer_batch_size).join(";");
+                    if let Err(e) = sender_request.send(request).await {
+                        warn!("{}", e);
+                        stop_flag.store(true, Ordering::Release);
+                        break;
+                    }
                 }
+                let duration = Local::now().timestamp_millis() - start;
+                info!("sent {} network capture sql in {} ms", length, duration);
             }
             Err(_) => {
                 info!("network capture timeout triggered")
             }
         }
 
-        sleep(Duration::from_millis(config.producer_frequency.unwrap())).await;
+        if receiver_capture.is_empty() {
+            sleep(Duration::from_millis(config.producer_frequency.unwrap())).await;
+        }
     }
 
     Ok(())
@@ -1,3 +1,6 @@
+use chrono::DateTime;
+use lsof::lsof::activity::FileActivity;
+use lsof::lsof::library::Library;
 use lsof::lsof::OpenFile;
 use network::capture::application::http::Http;
 use network::capture::application::tls::Tls;
@@ -17,8 +20,13 @@ use pnet::packet::ipv6::Ipv6;
 use pnet::packet::tcp::Tcp;
 use pnet::packet::udp::Udp;
 use pnet::packet::PrimitiveValues;
+use ps::ps::environ::Variable;
+use ps::ps::event::ProcessEvent;
+use ps::ps::executable::Executable;
+use ps::ps::thread::Thread;
 use ps::ps::Process;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::net::IpAddr;
 use uuid::Uuid;
 
@@ -74,18 +82,49 @@ pub fn concat_requests(requests: Vec<String>, batch_size: usize) -> Vec<String>
 
 impl BronzeBatch for Process {
     fn get_insert_header() -> String {
-        r#"INSERT INTO bronze_process_list (pid, ppid, uid, lstart, pcpu, pmem, status, command, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+        r#"INSERT INTO bronze_process_list (pid, ppid, uid, euid, gid, egid, lstart, pcpu, pmem, utime, stime, rss, vsz, threads, nice, priority, tty, exe, read_bytes, write_bytes, cgroup, container_id, pod_uid, container_runtime, systemd_unit, systemd_slice, mnt_ns, net_ns, cap_effective, cap_permitted, cap_bounding, capabilities, no_new_privs, seccomp, lsm_label, status, command, created_at, inserted_at, brz_ingestion_duration) VALUES "#
             .to_string()
     }
 
     fn to_insert_value(&self) -> String {
-        format!("({}, {}, {}, TO_TIMESTAMP({}), {}, {}, '{}', '{}', EPOCH_MS({8})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({8})::TIMESTAMP))",
+        let cgroup = self.cgroup.as_ref();
+        let security = self.security.as_ref();
+        format!("({}, {}, {}, {}, {}, {}, TO_TIMESTAMP({}), {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, '{}', '{}', EPOCH_MS({37})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({37})::TIMESTAMP))",
             self.pid,
             self.ppid,
             self.uid,
+            self.euid,
+            self.gid,
+            self.egid,
             self.lstart,
             self.pcpu,
             self.pmem,
+            option_number_to_sql(&self.utime),
+            option_number_to_sql(&self.stime),
+            self.rss,
+            self.vsz,
+            option_number_to_sql(&self.threads),
+            self.nice,
+            self.priority,
+            option_text_to_sql(&self.tty),
+            option_text_to_sql(&self.exe),
+            option_number_to_sql(&self.read_bytes),
+            option_number_to_sql(&self.write_bytes),
+            option_text_to_sql(&cgroup.map(|cgroup| cgroup.path.clone())),
+            option_text_to_sql(&cgroup.and_then(|cgroup| cgroup.container_id.clone())),
+            option_text_to_sql(&cgroup.and_then(|cgroup| cgroup.pod_uid.clone())),
+            option_text_to_sql(&cgroup.and_then(|cgroup| cgroup.runtime.clone())),
+            option_text_to_sql(&cgroup.and_then(|cgroup| cgroup.systemd_unit.clone())),
+            option_text_to_sql(&cgroup.and_then(|cgroup| cgroup.systemd_slice.clone())),
+            option_number_to_sql(&self.mnt_ns),
+            option_number_to_sql(&self.net_ns),
+            option_number_to_sql(&security.map(|security| security.cap_effective)),
+            option_number_to_sql(&security.map(|security| security.cap_permitted)),
+            option_number_to_sql(&security.map(|security| security.cap_bounding)),
+            option_text_to_sql(&security.map(|security| security.effective_names().join(","))),
+            option_number_to_sql(&security.map(|security| security.no_new_privs)),
+            option_text_to_sql(&security.map(|security| security.seccomp.clone())),
+            option_text_to_sql(&security.and_then(|security| security.lsm_label.clone())),
             self.status,
             self.command.replace('\'', "\""),
             self.created_at
@@ -93,15 +132,38 @@ impl BronzeBatch for Process {
     }
 }
 
+impl BronzeBatch for Thread {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_thread_list (pid, tid, name, state, utime, stime, processor, lstart, started_at, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"({}, {}, '{}', '{}', {}, {}, {}, TO_TIMESTAMP({}), TO_TIMESTAMP({}), EPOCH_MS({9})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({9})::TIMESTAMP))"#,
+            self.pid,
+            self.tid,
+            self.name.replace('\'', "''"),
+            self.state,
+            self.utime,
+            self.stime,
+            option_number_to_sql(&self.processor),
+            self.lstart,
+            self.started_at,
+            self.created_at
+        )
+    }
+}
+
 impl BronzeBatch for OpenFile {
     fn get_insert_header() -> String {
-        r#"INSERT INTO bronze_open_files (command, pid, uid, fd, type, device, size, node, name, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+        r#"INSERT INTO bronze_open_files (command, pid, uid, fd, type, device, size, node, name, ip_source_address, ip_source_port, ip_destination_address, ip_destination_port, access, lock, file_offset, protocol, tcp_state, recv_queue, send_queue, peer_node, link_count, created_at, inserted_at, brz_ingestion_duration) VALUES "#
             .to_string()
     }
 
     fn to_insert_value(&self) -> String {
         format!(
-            r#"('{}', {}, {}, '{}', '{}', '{}', {}, '{}', '{}', EPOCH_MS({9})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({9})::TIMESTAMP))"#,
+            r#"('{}', {}, {}, '{}', '{}', '{}', {}, '{}', '{}', {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, EPOCH_MS({22})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({22})::TIMESTAMP))"#,
             self.command.replace('\'', "\""),
             self.pid,
             self.uid,
@@ -111,6 +173,119 @@ impl BronzeBatch for OpenFile {
             self.size,
             self.node,
             self.name.replace('\'', "\""),
+            option_text_to_sql(&self.ip_source_address),
+            option_text_to_sql(&self.ip_source_port),
+            option_text_to_sql(&self.ip_destination_address),
+            option_text_to_sql(&self.ip_destination_port),
+            option_text_to_sql(&self.access),
+            option_text_to_sql(&self.lock),
+            option_number_to_sql(&self.offset),
+            option_text_to_sql(&self.protocol),
+            option_text_to_sql(&self.tcp_state),
+            option_number_to_sql(&self.recv_queue),
+            option_number_to_sql(&self.send_queue),
+            option_text_to_sql(&self.peer_node),
+            option_number_to_sql(&self.link_count),
+            self.created_at
+        )
+    }
+}
+
+impl BronzeBatch for ProcessEvent {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_process_event (event, pid, ppid, started_at, command, exit_code, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"('{}', {}, {}, {}, {}, {}, EPOCH_MS({6})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({6})::TIMESTAMP))"#,
+            self.event,
+            self.pid,
+            option_number_to_sql(&self.ppid),
+            option_timestamp_to_sql(&self.started_at),
+            option_text_to_sql(&self.command),
+            option_number_to_sql(&self.exit_code),
+            self.created_at
+        )
+    }
+}
+
+impl BronzeBatch for FileActivity {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_file_activity (event, path, directory, cookie, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"('{}', '{}', {}, {}, EPOCH_MS({4})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({4})::TIMESTAMP))"#,
+            self.event,
+            self.path.replace('\'', "''"),
+            self.directory,
+            option_number_to_sql(&self.cookie),
+            self.created_at
+        )
+    }
+}
+
+impl BronzeBatch for Variable {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_process_environ (pid, started_at, key, value, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"({}, TO_TIMESTAMP({}), '{}', '{}', EPOCH_MS({4})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({4})::TIMESTAMP))"#,
+            self.pid,
+            self.lstart,
+            self.key.replace('\'', "''"),
+            self.value.replace('\'', "''"),
+            self.created_at
+        )
+    }
+}
+
+impl BronzeBatch for Library {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_process_library (pid, command, path, device, inode, deleted, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"({}, '{}', '{}', '{}', {}, {}, EPOCH_MS({6})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({6})::TIMESTAMP))"#,
+            self.pid,
+            self.command.replace('\'', "''"),
+            self.path.replace('\'', "''"),
+            self.device,
+            self.inode,
+            self.deleted,
+            self.created_at
+        )
+    }
+}
+
+impl BronzeBatch for Executable {
+    fn get_insert_header() -> String {
+        r#"INSERT INTO bronze_process_executable (pid, started_at, path, device, inode, size, modified_at, sha256, deleted, replaced, created_at, inserted_at, brz_ingestion_duration) VALUES "#
+            .to_string()
+    }
+
+    fn to_insert_value(&self) -> String {
+        format!(
+            r#"({}, TO_TIMESTAMP({}), '{}', {}, {}, {}, TO_TIMESTAMP({}), '{}', {}, {}, EPOCH_MS({10})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({10})::TIMESTAMP))"#,
+            self.pid,
+            self.lstart,
+            self.path.replace('\'', "''"),
+            self.device,
+            self.inode,
+            self.size,
+            self.modified_at,
+            self.sha256,
+            self.deleted,
+            self.replaced,
             self.created_at
         )
     }
@@ -118,7 +293,6 @@ impl BronzeBatch for OpenFile {
 
 impl Bronze for Capture {
     fn to_insert_sql(&self, _foreign_id: Option<u128>) -> String {
-        let clone = self.clone();
         let row_id = Uuid::new_v4().as_u128();
         let mut request_buffer = format!(
             r#"INSERT OR REPLACE INTO bronze_network_packet (
@@ -128,36 +302,59 @@ impl Bronze for Capture {
             created_at,
             inserted_at,
             brz_ingestion_duration
-            ) VALUES ({}, '{}', {}, EPOCH_MS({3})::TIMESTAMP, CURRENT_TIMESTAMP, AGE(EPOCH_MS({3})::TIMESTAMP));"#,
+            ) VALUES ({}, '{}', {}, '{3}'::TIMESTAMP_NS, CURRENT_TIMESTAMP, AGE('{3}'::TIMESTAMP));"#,
             row_id,
-            clone.device.name,
-            clone.packet.len(),
-            clone.created_at
+            self.device.name,
+            self.length,
+            DateTime::from_timestamp_nanos(self.created_at).format("%Y-%m-%d %H:%M:%S%.9f")
         );
 
-        request_buffer.push_str(&device_addresses_to_sql(&clone.device));
-
-        if clone.data_link.is_some() {
-            request_buffer.push_str(&clone.data_link.unwrap().to_insert_sql(Some(row_id)))
+        if let Some(data_link) = &self.data_link {
+            request_buffer.push_str(&data_link.to_insert_sql(Some(row_id)))
         }
 
-        if clone.network.is_some() {
-            request_buffer.push_str(&clone.network.unwrap().to_insert_sql(Some(row_id)))
+        if let Some(network) = &self.network {
+            request_buffer.push_str(&network.to_insert_sql(Some(row_id)))
         }
 
-        if clone.transport.is_some() {
-            request_buffer.push_str(&clone.transport.unwrap().to_insert_sql(Some(row_id)))
+        if let Some(transport) = &self.transport {
+            request_buffer.push_str(&transport.to_insert_sql(Some(row_id)))
         }
 
-        if clone.application.is_some() {
-            request_buffer.push_str(&clone.application.unwrap().to_insert_sql(Some(row_id)))
+        if let Some(application) = &self.application {
+            request_buffer.push_str(&application.to_insert_sql(Some(row_id)))
         }
 
         request_buffer
     }
 }
 
-fn device_addresses_to_sql(device: &Device) -> String {
+fn option_text_to_sql(value: &Option<String>) -> String {
+    if let Some(value) = value {
+        format!("'{}'", value.replace('\'', "''"))
+    } else {
+        "NULL".to_string()
+    }
+}
+
+fn option_number_to_sql<T: Display>(value: &Option<T>) -> String {
+    if let Some(value) = value {
+        value.to_string()
+    } else {
+        "NULL".to_string()
+    }
+}
+
+fn option_timestamp_to_sql(value: &Option<i64>) -> String {
+    if let Some(value) = value {
+        format!("TO_TIMESTAMP({})", value)
+    } else {
+        "NULL".to_string()
+    }
+}
+
+// Addresses are the same for every packet of an interface, they are written once per sink chunk.
+pub fn device_addresses_to_sql(device: &Device) -> String {
     let mut request_buffer = String::new();
 
     for address in &device.addresses {
@@ -638,8 +835,12 @@ fn bronze_tls(tls: Tls, packet_id: u128) -> String {
 mod tests {
     use super::*;
     use crate::pipeline::stage::tests::create_test_connection;
+    use lsof::lsof::activity::ActivityType;
     use lsof::lsof::{lsof, FileType};
-    use ps::ps::ps;
+    use ps::ps::event::EventType;
+    use ps::ps::{ps, Source};
+    use std::sync::Arc;
+    use std::time::Instant;
 
     #[derive(Debug)]
     struct BronzeBatchTest {
@@ -831,7 +1032,7 @@ mod tests {
     #[test]
     fn test_insert_processes() {
         let connection = create_test_connection();
-        let processes = ps().unwrap();
+        let processes = ps(&Source::COMMAND).unwrap();
         connection
             .execute_batch(&create_insert_batch_request(processes))
             .unwrap();
@@ -849,7 +1050,7 @@ mod tests {
     #[test]
     fn test_insert_open_files() {
         let connection = create_test_connection();
-        let processes = lsof(&FileType::ALL).unwrap();
+        let processes = lsof(&lsof::lsof::Source::COMMAND, &FileType::ALL).unwrap();
         connection
             .execute_batch(&create_insert_batch_request(processes))
             .unwrap();
@@ -864,17 +1065,207 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_insert_process_events() {
+        let connection = create_test_connection();
+        let events = vec![
+            ProcessEvent {
+                event: EventType::EXEC,
+                pid: 4242,
+                ppid: Some(1),
+                started_at: Some(1726842675),
+                command: Some("sh -c 'exit 3'".to_string()),
+                exit_code: None,
+                created_at: 1726842675123,
+            },
+            ProcessEvent {
+                event: EventType::EXIT,
+                pid: 4242,
+                ppid: None,
+                started_at: None,
+                command: None,
+                exit_code: Some(3),
+                created_at: 1726842675456,
+            },
+        ];
+        connection
+            .execute_batch(&create_insert_batch_request(events))
+            .unwrap();
+        let mut statement = connection
+            .prepare("SELECT count(*), max(exit_code), max(command) FROM bronze_process_event;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let exit_code: i32 = row.get(1).unwrap();
+            let command: String = row.get(2).unwrap();
+            assert_eq!(count, 2);
+            assert_eq!(exit_code, 3);
+            assert_eq!(command, "sh -c 'exit 3'");
+        }
+    }
+
+    #[test]
+    fn test_insert_process_environ() {
+        let connection = create_test_connection();
+        let variables = vec![
+            Variable {
+                pid: 4242,
+                lstart: 1726842675,
+                key: "PATH".to_string(),
+                value: "/usr/bin:/bin".to_string(),
+                created_at: 1726842675123,
+            },
+            Variable {
+                pid: 4242,
+                lstart: 1726842675,
+                key: "JAVA_OPTS".to_string(),
+                value: "-Dname='rstracer'".to_string(),
+                created_at: 1726842675123,
+            },
+        ];
+        connection
+            .execute_batch(&create_insert_batch_request(variables))
+            .unwrap();
+        let mut statement = connection
+            .prepare("SELECT count(*), max(value) FROM bronze_process_environ;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let value: String = row.get(1).unwrap();
+            assert_eq!(count, 2);
+            assert_eq!(value, "/usr/bin:/bin");
+        }
+    }
+
+    #[test]
+    fn test_insert_process_libraries() {
+        let connection = create_test_connection();
+        let libraries = vec![
+            Library {
+                pid: 4242,
+                command: "nginx".to_string(),
+                path: "/usr/lib/x86_64-linux-gnu/libc.so.6".to_string(),
+                device: "fe:00".to_string(),
+                inode: 395379,
+                deleted: false,
+                created_at: 1726842675123,
+            },
+            Library {
+                pid: 4242,
+                command: "nginx".to_string(),
+                path: "/usr/lib/x86_64-linux-gnu/libssl.so.3".to_string(),
+                device: "fe:00".to_string(),
+                inode: 396120,
+                deleted: true,
+                created_at: 1726842675123,
+            },
+        ];
+        connection
+            .execute_batch(&create_insert_batch_request(libraries))
+            .unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT count(*), count(*) FILTER (WHERE deleted) FROM bronze_process_library;",
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let deleted: usize = row.get(1).unwrap();
+            assert_eq!(count, 2);
+            assert_eq!(deleted, 1);
+        }
+    }
+
+    #[test]
+    fn test_insert_process_executables() {
+        let connection = create_test_connection();
+        let executables = vec![Executable {
+            pid: 4242,
+            lstart: 1726842675,
+            path: "/usr/sbin/nginx".to_string(),
+            device: 65024,
+            inode: 1835023,
+            size: 1226552,
+            modified_at: 1726000000,
+            sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            deleted: false,
+            replaced: true,
+            created_at: 1726842675123,
+        }];
+        connection
+            .execute_batch(&create_insert_batch_request(executables))
+            .unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT count(*), max(sha256), bool_or(replaced) FROM bronze_process_executable;",
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let sha256: String = row.get(1).unwrap();
+            let replaced: bool = row.get(2).unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(sha256.len(), 64);
+            assert!(replaced);
+        }
+    }
+
+    #[test]
+    fn test_insert_file_activities() {
+        let connection = create_test_connection();
+        let activities = vec![
+            FileActivity {
+                event: ActivityType::MOVEDFROM,
+                path: "/etc/app's.conf".to_string(),
+                directory: false,
+                cookie: Some(4242),
+                created_at: 1726842675123,
+            },
+            FileActivity {
+                event: ActivityType::CLOSEWRITE,
+                path: "/etc/app.conf".to_string(),
+                directory: false,
+                cookie: None,
+                created_at: 1726842675456,
+            },
+        ];
+        connection
+            .execute_batch(&create_insert_batch_request(activities))
+            .unwrap();
+        let mut statement = connection
+            .prepare("SELECT count(*), max(cookie), min(path) FROM bronze_file_activity;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let cookie: u32 = row.get(1).unwrap();
+            let path: String = row.get(2).unwrap();
+            assert_eq!(count, 2);
+            assert_eq!(cookie, 4242);
+            assert_eq!(path, "/etc/app's.conf");
+        }
+    }
+
     #[test]
     fn test_capture_to_network_sql() {
         let connection = create_test_connection();
-        let device = pcap::Device::lookup().unwrap().unwrap();
+        let device = Arc::new(pcap::Device::lookup().unwrap().unwrap());
         let packet = vec![
             204, 45, 27, 186, 195, 248, 248, 99, 63, 244, 10, 21, 8, 0, 69, 0, 0, 67, 129, 205, 0,
             0, 64, 17, 117, 60, 192, 168, 1, 79, 192, 168, 1, 1, 174, 55, 0, 53, 0, 47, 113, 146,
             86, 48, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 6, 116, 97, 105, 118, 101, 109, 3, 99, 111, 109,
             0, 0, 1, 0, 1, 0, 0, 41, 5, 192, 0, 0, 0, 0, 0, 0,
         ];
-        let capture = Capture::parse(&packet, &device).unwrap();
+        let capture = Capture::parse(&packet, &device, 1726842675123456789).unwrap();
         connection
             .execute_batch(&capture.to_insert_sql(None))
             .unwrap();
@@ -897,5 +1288,77 @@ mod tests {
             let count: usize = row.get(0).unwrap();
             assert_eq!(count, 6);
         }
+
+        let mut statement = connection
+            .prepare("SELECT EPOCH_NS(created_at) FROM bronze_network_packet;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let created_at: i64 = row.get(0).unwrap();
+            assert_eq!(created_at, 1726842675123456789);
+        }
+    }
+
+    // Packet rate of the capture sink, run with
+    // `cargo test --release -p rstracer -- --ignored test_capture_sink_rate --nocapture`.
+    #[test]
+    #[ignore]
+    fn test_capture_sink_rate() {
+        const PACKETS: usize = 100_000;
+        const CONSUMER_BATCH_SIZE: usize = 200;
+        let connection = create_test_connection();
+        let device = Arc::new(pcap::Device::from("eth0"));
+        let packet = vec![
+            204, 45, 27, 186, 195, 248, 248, 99, 63, 244, 10, 21, 8, 0, 69, 0, 0, 67, 129, 205, 0,
+            0, 64, 17, 117, 60, 192, 168, 1, 79, 192, 168, 1, 1, 174, 55, 0, 53, 0, 47, 113, 146,
+            86, 48, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 6, 116, 97, 105, 118, 101, 109, 3, 99, 111, 109,
+            0, 0, 1, 0, 1, 0, 0, 41, 5, 192, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let start = Instant::now();
+        let captures: Vec<Capture> = (0..PACKETS)
+            .map(|i| Capture::parse(&packet, &device, 1726842675123456789 + i as i64).unwrap())
+            .collect();
+        let parse_duration = start.elapsed();
+
+        let start = Instant::now();
+        let requests: Vec<String> = captures
+            .chunks(CONSUMER_BATCH_SIZE)
+            .map(|chunk| {
+                let mut values: Vec<String> = chunk
+                    .iter()
+                    .map(|capture| capture.to_insert_sql(None))
+                    .collect();
+                values.push(device_addresses_to_sql(&device));
+                concat_requests(values, CONSUMER_BATCH_SIZE).join(";")
+            })
+            .collect();
+        let sql_duration = start.elapsed();
+
+        let start = Instant::now();
+        for request in &requests {
+            connection.execute_batch(request).unwrap();
+        }
+        let insert_duration = start.elapsed();
+
+        let rate = |duration: std::time::Duration| PACKETS as f64 / duration.as_secs_f64();
+        println!(
+            "{} packets: parse {:.0}/s, sql {:.0}/s, insert {:.0}/s",
+            PACKETS,
+            rate(parse_duration),
+            rate(sql_duration),
+            rate(insert_duration)
+        );
+
+        let mut statement = connection
+            .prepare("SELECT count(*) FROM bronze_network_packet;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            assert_eq!(count, PACKETS);
+        }
     }
 }
@@ -1,12 +1,18 @@
 const GOLD_DIM_PROCESS: &str = r#"
-INSERT OR REPLACE INTO gold_dim_process BY NAME
+INSERT INTO gold_dim_process BY NAME
 (
     SELECT
         pid,
         ppid,
         uid,
+        euid,
+        gid,
+        egid,
+        user_name,
         command,
         full_command,
+        exe,
+        tty,
         started_at,
         CURRENT_TIMESTAMP AS inserted_at
     FROM
@@ -15,15 +21,349 @@ INSERT OR REPLACE INTO gold_dim_process BY NAME
             pro.pid,
             pro.ppid,
             pro.uid,
+            pro.euid,
+            pro.gid,
+            pro.egid,
+            usr.name AS user_name,
             ofn.command AS command,
             pro.command AS full_command,
+            pro.exe,
+            pro.tty,
             pro.lstart AS started_at,
             row_number() OVER (PARTITION BY pro.pid, pro.lstart ORDER BY pro.inserted_at DESC) AS row_num
         FROM silver_process_list pro
         LEFT JOIN silver_open_files ofn ON pro.pid = ofn.pid
+        LEFT JOIN (SELECT uid, MIN(name) AS name FROM gold_file_user GROUP BY uid) usr ON pro.uid = usr.uid
     )
     WHERE ROW_NUM = 1
-);"#;
+)
+ON CONFLICT DO UPDATE SET
+    ppid = EXCLUDED.ppid,
+    uid = EXCLUDED.uid,
+    euid = EXCLUDED.euid,
+    gid = EXCLUDED.gid,
+    egid = EXCLUDED.egid,
+    user_name = EXCLUDED.user_name,
+    command = EXCLUDED.command,
+    full_command = EXCLUDED.full_command,
+    exe = EXCLUDED.exe,
+    tty = EXCLUDED.tty,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
+const GOLD_DIM_PROCESS_FROM_EVENT: &str = r#"
+INSERT INTO gold_dim_process BY NAME
+(
+    SELECT
+        pid,
+        ppid,
+        REGEXP_EXTRACT(full_command, '^(\S*/)?([^/\s]+)', 2) AS command,
+        full_command,
+        started_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+    (
+        SELECT
+            pid,
+            started_at,
+            ARG_MAX(ppid, created_at) FILTER (WHERE ppid IS NOT NULL) AS ppid,
+            ARG_MAX(command, created_at) FILTER (WHERE command IS NOT NULL) AS full_command
+        FROM silver_process_event
+        WHERE event IN ('fork', 'exec') AND started_at IS NOT NULL
+        GROUP BY pid, started_at
+    )
+)
+ON CONFLICT DO UPDATE SET
+    ppid = COALESCE(ppid, EXCLUDED.ppid),
+    command = COALESCE(command, EXCLUDED.command),
+    full_command = COALESCE(full_command, EXCLUDED.full_command)
+;
+UPDATE gold_dim_process
+SET
+    exit_code = ext.exit_code,
+    exited_at = ext.exited_at
+FROM
+(
+    SELECT
+        dim.pid,
+        dim.started_at,
+        evt.exit_code,
+        evt.created_at AS exited_at,
+        ROW_NUMBER() OVER (PARTITION BY evt._id ORDER BY dim.started_at DESC) AS row_num
+    FROM silver_process_event evt
+    JOIN gold_dim_process dim
+        ON evt.pid = dim.pid
+        AND dim.started_at = COALESCE(evt.started_at, dim.started_at)
+        AND dim.started_at <= evt.created_at
+    WHERE evt.event = 'exit'
+) ext
+WHERE ext.row_num = 1
+    AND gold_dim_process.pid = ext.pid
+    AND gold_dim_process.started_at = ext.started_at
+;"#;
+
+const GOLD_DIM_PROCESS_CONTAINER: &str = r#"
+INSERT INTO gold_dim_process_container BY NAME
+(
+    SELECT
+        pid,
+        started_at,
+        container_id,
+        pod_uid,
+        container_runtime,
+        systemd_unit,
+        systemd_slice,
+        cgroup,
+        mnt_ns,
+        net_ns,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+    (
+        SELECT
+            pid,
+            lstart AS started_at,
+            container_id,
+            pod_uid,
+            container_runtime,
+            systemd_unit,
+            systemd_slice,
+            cgroup,
+            mnt_ns,
+            net_ns,
+            ROW_NUMBER() OVER (PARTITION BY pid, lstart ORDER BY created_at DESC) AS row_num
+        FROM silver_process_list
+        WHERE cgroup IS NOT NULL OR mnt_ns IS NOT NULL OR net_ns IS NOT NULL
+    )
+    WHERE row_num = 1
+)
+ON CONFLICT DO UPDATE SET
+    container_id = EXCLUDED.container_id,
+    pod_uid = EXCLUDED.pod_uid,
+    container_runtime = EXCLUDED.container_runtime,
+    systemd_unit = EXCLUDED.systemd_unit,
+    systemd_slice = EXCLUDED.systemd_slice,
+    cgroup = EXCLUDED.cgroup,
+    mnt_ns = EXCLUDED.mnt_ns,
+    net_ns = EXCLUDED.net_ns,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
+const GOLD_DIM_PROCESS_TREE: &str = r#"
+INSERT INTO gold_dim_process_tree BY NAME
+(
+    WITH RECURSIVE process AS (
+        SELECT
+            pid,
+            ppid,
+            started_at,
+            exited_at,
+            COALESCE(command, REGEXP_EXTRACT(full_command, '^(\S*/)?([^/\s]+)', 2), pid::TEXT) AS command
+        FROM gold_dim_process
+    ),
+    parent AS (
+        SELECT
+            chi.pid,
+            chi.started_at,
+            chi.command,
+            par.pid AS ppid,
+            par.started_at AS parent_started_at
+        FROM process chi
+        LEFT JOIN process par
+            ON par.pid = chi.ppid
+            AND par.pid <> chi.pid
+            AND par.started_at <= chi.started_at
+            AND (par.exited_at IS NULL OR par.exited_at >= chi.started_at)
+        QUALIFY ROW_NUMBER() OVER (PARTITION BY chi.pid, chi.started_at ORDER BY par.started_at DESC NULLS LAST) = 1
+    ),
+    tree AS (
+        SELECT
+            pid,
+            started_at,
+            ppid,
+            parent_started_at,
+            0 AS depth,
+            pid AS root_pid,
+            started_at AS root_started_at,
+            command AS ancestor_path
+        FROM parent
+        WHERE ppid IS NULL
+        UNION ALL
+        SELECT
+            par.pid,
+            par.started_at,
+            par.ppid,
+            par.parent_started_at,
+            tree.depth + 1 AS depth,
+            tree.root_pid,
+            tree.root_started_at,
+            tree.ancestor_path || ' > ' || par.command AS ancestor_path
+        FROM parent par
+        JOIN tree ON par.ppid = tree.pid AND par.parent_started_at = tree.started_at
+        WHERE tree.depth < 256
+    )
+    SELECT
+        pid,
+        started_at,
+        ppid,
+        parent_started_at,
+        depth,
+        root_pid,
+        root_started_at,
+        ancestor_path,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM tree
+)
+ON CONFLICT DO UPDATE SET
+    ppid = EXCLUDED.ppid,
+    parent_started_at = EXCLUDED.parent_started_at,
+    depth = EXCLUDED.depth,
+    root_pid = EXCLUDED.root_pid,
+    root_started_at = EXCLUDED.root_started_at,
+    ancestor_path = EXCLUDED.ancestor_path,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
+const GOLD_DIM_PROCESS_SECURITY: &str = r#"
+INSERT INTO gold_dim_process_security BY NAME
+(
+    SELECT
+        pid,
+        started_at,
+        cap_effective,
+        cap_permitted,
+        cap_bounding,
+        capabilities,
+        no_new_privs,
+        seccomp,
+        lsm_label,
+        cap_effective <> 0 AS privileged,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+    (
+        SELECT
+            pid,
+            lstart AS started_at,
+            cap_effective,
+            cap_permitted,
+            cap_bounding,
+            capabilities,
+            no_new_privs,
+            seccomp,
+            lsm_label,
+            ROW_NUMBER() OVER (PARTITION BY pid, lstart ORDER BY created_at DESC) AS row_num
+        FROM silver_process_list
+        WHERE cap_effective IS NOT NULL
+    )
+    WHERE row_num = 1
+)
+ON CONFLICT DO UPDATE SET
+    cap_effective = EXCLUDED.cap_effective,
+    cap_permitted = EXCLUDED.cap_permitted,
+    cap_bounding = EXCLUDED.cap_bounding,
+    capabilities = EXCLUDED.capabilities,
+    no_new_privs = EXCLUDED.no_new_privs,
+    seccomp = EXCLUDED.seccomp,
+    lsm_label = EXCLUDED.lsm_label,
+    privileged = EXCLUDED.privileged,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
+const GOLD_DIM_PROCESS_ENVIRON: &str = r#"
+INSERT INTO gold_dim_process_environ BY NAME
+(
+    SELECT
+        pid,
+        started_at,
+        key,
+        value,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM silver_process_environ
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY pid, started_at, key ORDER BY created_at DESC) = 1
+)
+ON CONFLICT DO UPDATE SET
+    value = EXCLUDED.value,
+    inserted_at = EXCLUDED.inserted_at
+;
+UPDATE gold_dim_process_environ env
+SET inserted_at = pro.inserted_at
+FROM gold_dim_process pro
+WHERE env.pid = pro.pid
+AND env.started_at = pro.started_at
+AND env.inserted_at < pro.inserted_at
+;"#;
+
+const GOLD_DIM_PROCESS_LIBRARY: &str = r#"
+INSERT INTO gold_dim_process_library BY NAME
+(
+    SELECT
+        pid,
+        started_at,
+        command,
+        path,
+        device,
+        inode,
+        deleted,
+        first_seen_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+    (
+        SELECT
+            lib.pid,
+            pro.started_at,
+            lib.command,
+            lib.path,
+            lib.device,
+            lib.inode,
+            ARG_MAX(lib.deleted, lib.created_at) AS deleted,
+            MIN(lib.created_at) AS first_seen_at
+        FROM silver_process_library lib
+        ASOF JOIN gold_dim_process pro
+            ON lib.pid = pro.pid AND lib.created_at >= pro.started_at
+        GROUP BY lib.pid, pro.started_at, lib.command, lib.path, lib.device, lib.inode
+    )
+)
+ON CONFLICT DO UPDATE SET
+    deleted = deleted OR EXCLUDED.deleted,
+    first_seen_at = LEAST(first_seen_at, EXCLUDED.first_seen_at),
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
+const GOLD_DIM_EXECUTABLE: &str = r#"
+INSERT INTO gold_dim_executable BY NAME
+(
+    SELECT
+        pid,
+        started_at,
+        path,
+        device,
+        inode,
+        size,
+        modified_at,
+        sha256,
+        deleted,
+        replaced,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM silver_process_executable
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY pid, started_at ORDER BY created_at DESC) = 1
+)
+ON CONFLICT DO UPDATE SET
+    path = EXCLUDED.path,
+    device = EXCLUDED.device,
+    inode = EXCLUDED.inode,
+    size = EXCLUDED.size,
+    modified_at = EXCLUDED.modified_at,
+    sha256 = EXCLUDED.sha256,
+    deleted = EXCLUDED.deleted,
+    replaced = EXCLUDED.replaced,
+    inserted_at = EXCLUDED.inserted_at
+;
+UPDATE gold_dim_executable exe
+SET inserted_at = pro.inserted_at
+FROM gold_dim_process pro
+WHERE exe.pid = pro.pid
+AND exe.started_at = pro.started_at
+AND exe.inserted_at < pro.inserted_at
+;"#;
 
 const GOLD_DIM_FILE_REG: &str = r#"
 INSERT INTO gold_dim_file_reg BY NAME
@@ -50,7 +390,7 @@ INSERT INTO gold_dim_file_reg BY NAME
                 ROW_NUMBER() OVER (PARTITION BY pid, fd, node ORDER BY created_at ASC) AS row_num
             FROM
                 silver_open_files
-            WHERE UPPER(type) NOT IN ('IPV4', 'IPV6')
+            WHERE UPPER(type) NOT IN ('IPV4', 'IPV6', 'UNIX', 'FIFO', 'PIPE')
         )
     WHERE row_num = 1
 )
@@ -58,6 +398,48 @@ ON CONFLICT DO UPDATE SET
     inserted_at = EXCLUDED.inserted_at
 ;"#;
 
+const GOLD_DIM_FILE_DELETED_OPEN: &str = r#"
+INSERT INTO gold_dim_file_deleted_open BY NAME
+(
+    SELECT
+        pid,
+        uid,
+        fd,
+        device,
+        node,
+        command,
+        REGEXP_REPLACE(name, ' \(deleted\)$', '') AS name,
+        size AS reclaimable_size,
+        started_at,
+        created_at AS last_seen_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+        (
+            SELECT
+                pid,
+                uid,
+                fd,
+                device,
+                node,
+                command,
+                name,
+                size,
+                created_at,
+                MIN(created_at) OVER (PARTITION BY pid, fd, node) AS started_at,
+                ROW_NUMBER() OVER (PARTITION BY pid, fd, node ORDER BY created_at DESC) AS row_num
+            FROM
+                silver_open_files
+            WHERE type = 'REG'
+            AND link_count = 0
+        )
+    WHERE row_num = 1
+)
+ON CONFLICT DO UPDATE SET
+    reclaimable_size = EXCLUDED.reclaimable_size,
+    last_seen_at = EXCLUDED.last_seen_at,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
 const GOLD_DIM_NETWORK_INTERFACE: &str = r#"
 INSERT INTO gold_dim_network_interface BY NAME
 (
@@ -90,8 +472,8 @@ ON CONFLICT DO UPDATE SET
 const GOLD_DIM_NETWORK_SOCKET: &str = r#"
 INSERT INTO gold_dim_network_socket BY NAME
 (
-     SELECT
-        HASH(pid, source_address, source_port, destination_address, destination_port) AS _id,
+    SELECT
+        socket_id AS _id,
         pid,
         uid,
         command,
@@ -101,43 +483,8 @@ INSERT INTO gold_dim_network_socket BY NAME
         destination_port::USMALLINT AS destination_port,
         created_at AS started_at,
         CURRENT_TIMESTAMP AS inserted_at
-    FROM
-    (
-        SELECT
-        *
-        FROM
-        (
-            SELECT
-	            ofn.pid,
-	            ofn.uid,
-	            ofn.command,
-	            CASE
-	                WHEN ofn.ip_source_address = '*' THEN NULL
-	                ELSE REPLACE(COALESCE(hos1.address, ofn.ip_source_address), '-', ':')
-	            END AS source_address,
-	            CASE
-	                WHEN ofn.ip_source_port IN ('*', '') THEN NULL
-	                ELSE COALESCE(ser1.port::TEXT, ofn.ip_source_port)
-	            END AS source_port,
-	            CASE
-	                WHEN ofn.ip_destination_address IN ('*', '') THEN NULL
-	                ELSE COALESCE(hos2.address, ofn.ip_destination_address)
-	            END AS destination_address,
-	            CASE
-	                WHEN ofn.ip_destination_port IN ('*', '') THEN NULL
-	                ELSE COALESCE(ser2.port::TEXT, ofn.ip_destination_port)
-	            END AS destination_port,
-	            ofn.created_at,
-                ROW_NUMBER() OVER (PARTITION BY pid, ip_source_address, ip_source_port, ip_destination_address, ip_destination_port ORDER BY created_at ASC) AS row_num
-            FROM silver_open_files ofn
-	        LEFT JOIN gold_file_host hos1 ON LOWER(ofn.ip_source_address) = LOWER(hos1.name)
-	        LEFT JOIN gold_file_host hos2 ON LOWER(ofn.ip_destination_address) = LOWER(hos2.name)
-	        LEFT JOIN gold_file_service ser1 ON LOWER(ofn.ip_source_port) = LOWER(ser1.name)
-	        LEFT JOIN gold_file_service ser2 ON LOWER(ofn.ip_destination_port) = LOWER(ser2.name)
-            WHERE UPPER(ofn.type) IN ('IPV4', 'IPV6')
-        )
-        WHERE row_num = 1
-    )
+    FROM gold_view_network_socket
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY socket_id ORDER BY created_at ASC) = 1
 )
 ON CONFLICT DO UPDATE SET
     inserted_at = EXCLUDED.inserted_at;
@@ -292,11 +639,39 @@ SELECT DISTINCT
     created_at,
     pcpu,
     pmem,
+    utime,
+    stime,
+    rss,
+    vsz,
+    threads,
+    nice,
+    priority,
+    read_bytes,
+    write_bytes,
     CURRENT_TIMESTAMP AS inserted_at
 FROM silver_process_list
 )
 ;"#;
 
+const GOLD_FACT_THREAD: &str = r#"
+INSERT OR REPLACE INTO gold_fact_thread BY NAME
+(
+SELECT DISTINCT
+    pid,
+    lstart AS started_at,
+    tid,
+    created_at,
+    name,
+    state,
+    utime,
+    stime,
+    processor,
+    started_at AS thread_started_at,
+    CURRENT_TIMESTAMP AS inserted_at
+FROM silver_thread_list
+)
+;"#;
+
 const GOLD_FACT_FILE_REG: &str = r#"
 INSERT OR REPLACE INTO gold_fact_file_reg BY NAME
 (
@@ -308,7 +683,56 @@ SELECT DISTINCT
     size,
     CURRENT_TIMESTAMP AS inserted_at
 FROM silver_open_files
-WHERE UPPER(type) NOT IN ('IPV4', 'IPV6')
+WHERE UPPER(type) NOT IN ('IPV4', 'IPV6', 'UNIX', 'FIFO', 'PIPE')
+)
+;"#;
+
+const GOLD_FACT_FILE_ACTIVITY: &str = r#"
+INSERT OR REPLACE INTO gold_fact_file_activity BY NAME
+(
+    SELECT
+        event,
+        path,
+        directory,
+        cookie,
+        pid,
+        command,
+        created_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM
+        (
+            SELECT
+                act.event,
+                act.path,
+                act.directory,
+                act.cookie,
+                reg.pid,
+                reg.command,
+                act.created_at,
+                ROW_NUMBER() OVER (PARTITION BY act.path, act.event, act.created_at ORDER BY reg.started_at DESC NULLS LAST) AS row_num
+            FROM silver_file_activity act
+            LEFT JOIN gold_dim_file_reg reg
+            ON reg.name = act.path
+            AND act.created_at BETWEEN reg.started_at - INTERVAL 1 MINUTE AND reg.inserted_at + INTERVAL 1 MINUTE
+        )
+    WHERE row_num = 1
+)
+;"#;
+
+const GOLD_FACT_NETWORK_SOCKET: &str = r#"
+INSERT OR REPLACE INTO gold_fact_network_socket BY NAME
+(
+    SELECT
+        socket_id,
+        pid,
+        tcp_state AS state,
+        recv_queue,
+        send_queue,
+        created_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM gold_view_network_socket
+    WHERE tcp_state IS NOT NULL OR recv_queue IS NOT NULL
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY socket_id, created_at ORDER BY fd) = 1
 )
 ;"#;
 
@@ -391,6 +815,102 @@ INSERT OR REPLACE INTO gold_fact_process_network BY NAME
 );
 "#;
 
+const GOLD_FACT_PROCESS_IPC: &str = r#"
+INSERT OR REPLACE INTO gold_fact_process_ipc BY NAME
+(
+    WITH ipc AS
+    (
+        SELECT pid, fd, type, node, name, access, peer_node, created_at
+        FROM silver_open_files
+        WHERE type IN ('unix', 'FIFO', 'PIPE')
+    )
+    SELECT DISTINCT
+        ipc.pid,
+        ipc.fd,
+        peer.pid AS peer_pid,
+        peer.fd AS peer_fd,
+        ipc.type,
+        ipc.node,
+        COALESCE(NULLIF(ipc.name, ''), NULLIF(peer.name, '')) AS name,
+        ipc.created_at,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM ipc
+    INNER JOIN ipc peer ON ipc.created_at = peer.created_at AND ipc.pid <> peer.pid
+    WHERE (ipc.type = 'unix' AND peer.type = 'unix' AND ipc.peer_node = peer.node)
+    OR (
+        ipc.type IN ('FIFO', 'PIPE')
+        AND peer.type = ipc.type
+        AND ipc.node = peer.node
+        AND ipc.access IN ('w', 'u')
+        AND peer.access IN ('r', 'u')
+    )
+)
+;"#;
+
+const GOLD_FACT_PROCESS_EVENT: &str = r#"
+INSERT INTO gold_fact_process_event BY NAME
+(
+    WITH snapshot AS
+    (
+        SELECT DISTINCT created_at FROM gold_fact_process
+    ),
+    process AS
+    (
+        SELECT
+            pid,
+            started_at,
+            MAX(created_at) AS last_seen_at,
+            LAG(started_at) OVER (PARTITION BY pid ORDER BY started_at) IS NOT NULL AS pid_reused_before,
+            LEAD(started_at) OVER (PARTITION BY pid ORDER BY started_at) IS NOT NULL AS pid_reused_after
+        FROM gold_fact_process
+        GROUP BY pid, started_at
+    ),
+    lifecycle AS
+    (
+        SELECT
+            process.*,
+            (SELECT MIN(created_at) FROM snapshot WHERE snapshot.created_at > process.last_seen_at) AS missing_at
+        FROM process
+    )
+    SELECT
+        lif.pid,
+        lif.started_at,
+        'started' AS event,
+        lif.started_at AS event_at,
+        lif.last_seen_at,
+        dim.ppid,
+        dim.command,
+        dim.full_command,
+        lif.last_seen_at - lif.started_at AS lifetime,
+        lif.pid_reused_before AS pid_reused,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM lifecycle lif
+    LEFT JOIN gold_dim_process dim ON lif.pid = dim.pid AND lif.started_at = dim.started_at
+    WHERE lif.started_at > (SELECT MIN(created_at) FROM snapshot)
+    UNION ALL
+    SELECT
+        lif.pid,
+        lif.started_at,
+        'exited' AS event,
+        lif.missing_at AS event_at,
+        lif.last_seen_at,
+        dim.ppid,
+        dim.command,
+        dim.full_command,
+        lif.last_seen_at - lif.started_at AS lifetime,
+        lif.pid_reused_after AS pid_reused,
+        CURRENT_TIMESTAMP AS inserted_at
+    FROM lifecycle lif
+    LEFT JOIN gold_dim_process dim ON lif.pid = dim.pid AND lif.started_at = dim.started_at
+    WHERE lif.missing_at < (SELECT MAX(created_at) FROM snapshot)
+)
+ON CONFLICT DO UPDATE SET
+    last_seen_at = EXCLUDED.last_seen_at,
+    lifetime = EXCLUDED.lifetime,
+    pid_reused = EXCLUDED.pid_reused,
+    inserted_at = EXCLUDED.inserted_at
+;"#;
+
 const GOLD_TECH_TABLE_COUNT: &str = r#"
 INSERT INTO gold_tech_table_count BY NAME
 (
@@ -433,7 +953,31 @@ INSERT INTO gold_tech_table_count BY NAME
     SELECT 36 AS _id, 'gold_fact_process_network' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_process_network UNION
     SELECT 37 AS _id, 'gold_file_service' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_file_service UNION
     SELECT 38 AS _id, 'gold_file_host' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_file_host UNION
-    SELECT 39 AS _id, 'gold_file_user' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_file_user
+    SELECT 39 AS _id, 'gold_file_user' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_file_user UNION
+    SELECT 40 AS _id, 'gold_fact_process_event' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_process_event UNION
+    SELECT 41 AS _id, 'bronze_process_event' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_process_event UNION
+    SELECT 42 AS _id, 'silver_process_event' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_process_event UNION
+    SELECT 43 AS _id, 'gold_dim_process_container' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_process_container UNION
+    SELECT 44 AS _id, 'gold_dim_process_tree' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_process_tree UNION
+    SELECT 45 AS _id, 'bronze_thread_list' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_thread_list UNION
+    SELECT 46 AS _id, 'silver_thread_list' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_thread_list UNION
+    SELECT 47 AS _id, 'gold_fact_thread' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_thread UNION
+    SELECT 48 AS _id, 'gold_dim_process_security' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_process_security UNION
+    SELECT 49 AS _id, 'bronze_process_environ' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_process_environ UNION
+    SELECT 50 AS _id, 'silver_process_environ' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_process_environ UNION
+    SELECT 51 AS _id, 'gold_dim_process_environ' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_process_environ UNION
+    SELECT 52 AS _id, 'bronze_process_library' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_process_library UNION
+    SELECT 53 AS _id, 'silver_process_library' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_process_library UNION
+    SELECT 54 AS _id, 'gold_dim_process_library' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_process_library UNION
+    SELECT 55 AS _id, 'bronze_process_executable' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_process_executable UNION
+    SELECT 56 AS _id, 'silver_process_executable' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_process_executable UNION
+    SELECT 57 AS _id, 'gold_dim_executable' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_executable UNION
+    SELECT 58 AS _id, 'gold_fact_network_socket' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_network_socket UNION
+    SELECT 59 AS _id, 'gold_fact_process_ipc' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_process_ipc UNION
+    SELECT 60 AS _id, 'gold_dim_file_deleted_open' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_dim_file_deleted_open UNION
+    SELECT 61 AS _id, 'bronze_file_activity' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM bronze_file_activity UNION
+    SELECT 62 AS _id, 'silver_file_activity' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM silver_file_activity UNION
+    SELECT 63 AS _id, 'gold_fact_file_activity' AS name, count(*) AS max_count, count(*) AS last_count, CURRENT_TIMESTAMP AS inserted_at FROM gold_fact_file_activity
 )
 ON CONFLICT DO UPDATE SET
     inserted_at = EXCLUDED.inserted_at,
@@ -487,22 +1031,294 @@ ON CONFLICT DO UPDATE SET
     min_ingest = LEAST(min_ingest, EXCLUDED.min_ingest)
 ;"#;
 
-pub fn request() -> String {
+// CPU usage since the previous row of the same keys, or since the start for the first one.
+fn pcpu_interval_update(table: &str, keys: &[&str], origin: &str, silver_table: &str) -> String {
+    let join = keys
+        .iter()
+        .map(|key| format!("cur.{key} = pre.{key}"))
+        .collect::<Vec<String>>()
+        .join(" AND ");
+    let filter = keys
+        .iter()
+        .map(|key| format!("AND fac.{key} = delta.{key}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let columns = keys
+        .iter()
+        .map(|key| format!("cur.{key},"))
+        .collect::<Vec<String>>()
+        .join("\n        ");
+    format!(
+        r#"
+UPDATE {table} fac
+SET pcpu_interval = delta.pcpu_interval
+FROM
+(
+    SELECT
+        {columns}
+        cur.created_at,
+        100 * (cur.utime + cur.stime - COALESCE(pre.utime + pre.stime, 0))
+            / NULLIF(EPOCH_MS(cur.created_at) - EPOCH_MS(COALESCE(pre.created_at, cur.{origin})), 0) AS pcpu_interval
+    FROM {table} cur
+    ASOF LEFT JOIN {table} pre
+        ON {join} AND cur.created_at > pre.created_at
+    WHERE cur.pcpu_interval IS NULL
+    AND cur.utime IS NOT NULL
+    AND cur.stime IS NOT NULL
+    AND cur.created_at >= (SELECT MIN(created_at) FROM {silver_table})
+) delta
+WHERE fac.created_at = delta.created_at
+{filter}
+AND fac.pcpu_interval IS NULL
+;"#
+    )
+}
+
+// Replayed packets come from another time or host, they are not attributed to the local processes.
+pub fn request(replay: bool) -> String {
+    let process_network = if replay {
+        ""
+    } else {
+        GOLD_FACT_PROCESS_NETWORK
+    };
     format!(
-        "{} {} {} {} {} {} {} {} {} {} {} {} {} {}",
+        "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
         GOLD_DIM_PROCESS,
+        GOLD_DIM_PROCESS_FROM_EVENT,
+        GOLD_DIM_PROCESS_CONTAINER,
+        GOLD_DIM_PROCESS_TREE,
+        GOLD_DIM_PROCESS_SECURITY,
+        GOLD_DIM_PROCESS_ENVIRON,
+        GOLD_DIM_PROCESS_LIBRARY,
+        GOLD_DIM_EXECUTABLE,
         GOLD_DIM_FILE_REG,
+        GOLD_DIM_FILE_DELETED_OPEN,
         GOLD_DIM_NETWORK_INTERFACE,
         GOLD_DIM_NETWORK_SOCKET,
         GOLD_DIM_NETWORK_OPEN_PORT,
         GOLD_DIM_NETWORK_FOREIGN_IP,
         GOLD_DIM_NETWORK_HOST,
         GOLD_FACT_PROCESS,
+        pcpu_interval_update(
+            "gold_fact_process",
+            &["pid", "started_at"],
+            "started_at",
+            "silver_process_list"
+        ),
+        GOLD_FACT_THREAD,
+        pcpu_interval_update(
+            "gold_fact_thread",
+            &["pid", "started_at", "tid"],
+            "thread_started_at",
+            "silver_thread_list"
+        ),
+        GOLD_FACT_PROCESS_EVENT,
         GOLD_FACT_FILE_REG,
+        GOLD_FACT_FILE_ACTIVITY,
+        GOLD_FACT_NETWORK_SOCKET,
         GOLD_FACT_NETWORK_PACKET,
         GOLD_FACT_NETWORK_IP,
-        GOLD_FACT_PROCESS_NETWORK,
+        process_network,
+        GOLD_FACT_PROCESS_IPC,
         GOLD_TECH_TABLE_COUNT,
         GOLD_TECH_CHRONO
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::stage::tests::create_test_connection;
+    use duckdb::Connection;
+
+    // (pid, ppid, lstart, command) of a ps snapshot, `_id` is unique per snapshot index
+    fn insert_process_snapshot(
+        connection: &Connection,
+        index: u32,
+        created_at: &str,
+        processes: &[(u32, u32, &str, &str)],
+    ) {
+        let values: Vec<String> = processes
+            .iter()
+            .map(|(pid, ppid, lstart, command)| {
+                format!(
+                    "({}, {}, {}, '{}', 'S', '{}', '{}', CURRENT_TIMESTAMP)",
+                    index * 1000 + pid,
+                    pid,
+                    ppid,
+                    lstart,
+                    command,
+                    created_at
+                )
+            })
+            .collect();
+        connection
+            .execute_batch(&format!(
+                "INSERT INTO silver_process_list (_id, pid, ppid, lstart, status, command, created_at, inserted_at) VALUES {};",
+                values.join(",")
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_file_reg_excludes_ipc() {
+        let connection = create_test_connection();
+        connection
+            .execute_batch(
+                r#"
+INSERT INTO silver_open_files (_id, command, pid, uid, fd, type, node, name, created_at, inserted_at) VALUES
+    (1, 'sshd', 10, 0, '3u', 'unix', '4242', 'type=STREAM', '2024-09-20 14:31:15', CURRENT_TIMESTAMP),
+    (2, 'bash', 11, 0, '1w', 'FIFO', '4243', 'pipe', '2024-09-20 14:31:15', CURRENT_TIMESTAMP),
+    (3, 'bash', 12, 0, '0r', 'PIPE', '4243', 'pipe', '2024-09-20 14:31:15', CURRENT_TIMESTAMP),
+    (4, 'bash', 12, 0, '3r', 'REG', '4244', '/var/log/syslog', '2024-09-20 14:31:15', CURRENT_TIMESTAMP);
+"#,
+            )
+            .unwrap();
+        connection.execute_batch(&request(false)).unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT (SELECT count(*) FROM gold_dim_file_reg), (SELECT count(*) FROM gold_fact_file_reg);",
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let dim_count: usize = row.get(0).unwrap();
+            let fact_count: usize = row.get(1).unwrap();
+            assert_eq!(dim_count, 1);
+            assert_eq!(fact_count, 1);
+        }
+    }
+
+    #[test]
+    fn test_process_event() {
+        let connection = create_test_connection();
+        insert_process_snapshot(
+            &connection,
+            1,
+            "2024-09-20 10:00:00",
+            &[
+                (1, 0, "2024-09-20 09:00:00", "/sbin/init"),
+                (100, 1, "2024-09-20 09:30:00", "sleep 3600"),
+            ],
+        );
+        insert_process_snapshot(
+            &connection,
+            2,
+            "2024-09-20 10:00:10",
+            &[
+                (1, 0, "2024-09-20 09:00:00", "/sbin/init"),
+                (100, 1, "2024-09-20 10:00:05", "curl example.com"),
+                (200, 1, "2024-09-20 10:00:02", "sh -c ls"),
+            ],
+        );
+        connection.execute_batch(&request(false)).unwrap();
+        let mut statement = connection
+            .prepare(
+                r#"SELECT
+                    count(*) FILTER (WHERE event = 'started'),
+                    count(*) FILTER (WHERE event = 'started' AND pid_reused),
+                    count(*) FILTER (WHERE event = 'exited')
+                FROM gold_fact_process_event;"#,
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        // the latest snapshot may not be fully inserted yet, an exit is only confirmed by the next one
+        if let Some(row) = rows.next().unwrap() {
+            let started: usize = row.get(0).unwrap();
+            let reused: usize = row.get(1).unwrap();
+            let exited: usize = row.get(2).unwrap();
+            assert_eq!(started, 2);
+            assert_eq!(reused, 1);
+            assert_eq!(exited, 0);
+        }
+
+        insert_process_snapshot(
+            &connection,
+            3,
+            "2024-09-20 10:00:20",
+            &[
+                (1, 0, "2024-09-20 09:00:00", "/sbin/init"),
+                (100, 1, "2024-09-20 10:00:05", "curl example.com"),
+            ],
+        );
+        connection.execute_batch(&request(false)).unwrap();
+        let mut statement = connection
+            .prepare(
+                r#"SELECT pid, full_command, strftime(event_at, '%H:%M:%S'), EPOCH(lifetime)::BIGINT, pid_reused
+                FROM gold_fact_process_event
+                WHERE event = 'exited';"#,
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let pid: u32 = row.get(0).unwrap();
+            let full_command: String = row.get(1).unwrap();
+            let event_at: String = row.get(2).unwrap();
+            let lifetime: i64 = row.get(3).unwrap();
+            let pid_reused: bool = row.get(4).unwrap();
+            assert_eq!(pid, 100);
+            assert_eq!(full_command, "sleep 3600");
+            assert_eq!(event_at, "10:00:10");
+            assert_eq!(lifetime, 1800);
+            assert!(pid_reused);
+        }
+        assert!(rows.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_process_tree_reused_parent_pid() {
+        let connection = create_test_connection();
+        insert_process_snapshot(
+            &connection,
+            1,
+            "2024-09-20 09:10:00",
+            &[
+                (1, 0, "2024-09-20 08:00:00", "/sbin/init"),
+                (10, 1, "2024-09-20 09:00:00", "/bin/bash"),
+                (20, 10, "2024-09-20 09:05:00", "vim notes.txt"),
+            ],
+        );
+        // bash exited and its pid was reused by cron, vim still reports it as parent
+        insert_process_snapshot(
+            &connection,
+            2,
+            "2024-09-20 09:40:00",
+            &[
+                (1, 0, "2024-09-20 08:00:00", "/sbin/init"),
+                (10, 1, "2024-09-20 09:20:00", "/usr/sbin/cron -f"),
+                (20, 10, "2024-09-20 09:05:00", "vim notes.txt"),
+                (30, 10, "2024-09-20 09:30:00", "sh -c backup"),
+            ],
+        );
+        connection.execute_batch(&request(false)).unwrap();
+        let mut statement = connection
+            .prepare(
+                r#"SELECT pid, depth, root_pid, strftime(parent_started_at, '%H:%M'), ancestor_path
+                FROM gold_dim_process_tree
+                WHERE pid IN (20, 30)
+                ORDER BY pid;"#,
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        for (pid, parent_started_at, ancestor_path) in [
+            (20, "09:00", "init > bash > vim"),
+            (30, "09:20", "init > cron > sh"),
+        ] {
+            let row = rows.next().unwrap().unwrap();
+            let row_pid: u32 = row.get(0).unwrap();
+            let depth: u32 = row.get(1).unwrap();
+            let root_pid: u32 = row.get(2).unwrap();
+            let row_parent_started_at: String = row.get(3).unwrap();
+            let row_ancestor_path: String = row.get(4).unwrap();
+            assert_eq!(row_pid, pid);
+            assert_eq!(depth, 2);
+            assert_eq!(root_pid, 1);
+            assert_eq!(row_parent_started_at, parent_started_at);
+            assert_eq!(row_ancestor_path, ancestor_path);
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub mod file;
 pub mod gold;
 pub mod schema;
 pub mod silver;
+pub mod tech;
 pub mod vacuum;
 
 #[cfg(test)]
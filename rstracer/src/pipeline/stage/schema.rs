@@ -8,10 +8,39 @@ CREATE OR REPLACE TABLE bronze_process_list (
     _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_process_list_serial'),
     pid UINTEGER,
     ppid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
+    euid UINTEGER,
+    gid UINTEGER,
+    egid UINTEGER,
     lstart TIMESTAMP,
     pcpu FLOAT,
     pmem FLOAT,
+    utime UBIGINT,
+    stime UBIGINT,
+    rss UBIGINT,
+    vsz UBIGINT,
+    threads UINTEGER,
+    nice INTEGER,
+    priority INTEGER,
+    tty TEXT,
+    exe TEXT,
+    read_bytes UBIGINT,
+    write_bytes UBIGINT,
+    cgroup TEXT,
+    container_id TEXT,
+    pod_uid TEXT,
+    container_runtime TEXT,
+    systemd_unit TEXT,
+    systemd_slice TEXT,
+    mnt_ns UBIGINT,
+    net_ns UBIGINT,
+    cap_effective UBIGINT,
+    cap_permitted UBIGINT,
+    cap_bounding UBIGINT,
+    capabilities TEXT,
+    no_new_privs BOOLEAN,
+    seccomp TEXT,
+    lsm_label TEXT,
     status TEXT,
     command TEXT,
     created_at TIMESTAMP,
@@ -20,19 +49,131 @@ CREATE OR REPLACE TABLE bronze_process_list (
 );
 "#;
 
+const BRONZE_THREAD_LIST: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_thread_list_serial;
+CREATE OR REPLACE TABLE bronze_thread_list (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_thread_list_serial'),
+    pid UINTEGER,
+    tid UINTEGER,
+    name TEXT,
+    state TEXT,
+    utime UBIGINT,
+    stime UBIGINT,
+    processor UINTEGER,
+    lstart TIMESTAMP,
+    started_at TIMESTAMP,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
 const BRONZE_OPEN_FILES: &str = r#"
 CREATE SEQUENCE IF NOT EXISTS bronze_open_files_serial;
 CREATE OR REPLACE TABLE bronze_open_files (
     _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_open_files_serial'),
     command TEXT,
     pid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
     fd TEXT,
     type TEXT,
     device TEXT,
     size UBIGINT,
     node TEXT,
     name TEXT,
+    ip_source_address TEXT,
+    ip_source_port TEXT,
+    ip_destination_address TEXT,
+    ip_destination_port TEXT,
+    access TEXT,
+    lock TEXT,
+    file_offset UBIGINT,
+    protocol TEXT,
+    tcp_state TEXT,
+    recv_queue UBIGINT,
+    send_queue UBIGINT,
+    peer_node TEXT,
+    link_count UBIGINT,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
+const BRONZE_PROCESS_EVENT: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_process_event_serial;
+CREATE OR REPLACE TABLE bronze_process_event (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_process_event_serial'),
+    event TEXT,
+    pid UINTEGER,
+    ppid UINTEGER,
+    started_at TIMESTAMP,
+    command TEXT,
+    exit_code INTEGER,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
+const BRONZE_PROCESS_ENVIRON: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_process_environ_serial;
+CREATE OR REPLACE TABLE bronze_process_environ (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_process_environ_serial'),
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    key TEXT,
+    value TEXT,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
+const BRONZE_PROCESS_LIBRARY: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_process_library_serial;
+CREATE OR REPLACE TABLE bronze_process_library (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_process_library_serial'),
+    pid UINTEGER,
+    command TEXT,
+    path TEXT,
+    device TEXT,
+    inode UBIGINT,
+    deleted BOOLEAN,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
+const BRONZE_PROCESS_EXECUTABLE: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_process_executable_serial;
+CREATE OR REPLACE TABLE bronze_process_executable (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_process_executable_serial'),
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    path TEXT,
+    device UBIGINT,
+    inode UBIGINT,
+    size UBIGINT,
+    modified_at TIMESTAMP,
+    sha256 TEXT,
+    deleted BOOLEAN,
+    replaced BOOLEAN,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL
+);
+"#;
+
+const BRONZE_FILE_ACTIVITY: &str = r#"
+CREATE SEQUENCE IF NOT EXISTS bronze_file_activity_serial;
+CREATE OR REPLACE TABLE bronze_file_activity (
+    _id INTEGER PRIMARY KEY DEFAULT nextval('bronze_file_activity_serial'),
+    event TEXT,
+    path TEXT,
+    directory BOOLEAN,
+    cookie UINTEGER,
     created_at TIMESTAMP,
     inserted_at TIMESTAMP,
     brz_ingestion_duration INTERVAL
@@ -44,7 +185,7 @@ CREATE OR REPLACE TABLE bronze_network_packet (
     _id UHUGEINT PRIMARY KEY,
     interface TEXT,
     length UINTEGER,
-    created_at TIMESTAMP,
+    created_at TIMESTAMP_NS,
     inserted_at TIMESTAMP,
     brz_ingestion_duration INTERVAL
 );
@@ -268,10 +409,39 @@ CREATE OR REPLACE TABLE silver_process_list (
     _id INTEGER PRIMARY KEY,
     pid UINTEGER,
     ppid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
+    euid UINTEGER,
+    gid UINTEGER,
+    egid UINTEGER,
     lstart TIMESTAMP,
     pcpu FLOAT,
     pmem FLOAT,
+    utime UBIGINT,
+    stime UBIGINT,
+    rss UBIGINT,
+    vsz UBIGINT,
+    threads UINTEGER,
+    nice INTEGER,
+    priority INTEGER,
+    tty TEXT,
+    exe TEXT,
+    read_bytes UBIGINT,
+    write_bytes UBIGINT,
+    cgroup TEXT,
+    container_id TEXT,
+    pod_uid TEXT,
+    container_runtime TEXT,
+    systemd_unit TEXT,
+    systemd_slice TEXT,
+    mnt_ns UBIGINT,
+    net_ns UBIGINT,
+    cap_effective UBIGINT,
+    cap_permitted UBIGINT,
+    cap_bounding UBIGINT,
+    capabilities TEXT,
+    no_new_privs BOOLEAN,
+    seccomp TEXT,
+    lsm_label TEXT,
     status TEXT,
     command TEXT,
     created_at TIMESTAMP,
@@ -282,12 +452,31 @@ CREATE OR REPLACE TABLE silver_process_list (
 );
 "#;
 
+const SILVER_THREAD_LIST: &str = r#"
+CREATE OR REPLACE TABLE silver_thread_list (
+    _id INTEGER PRIMARY KEY,
+    pid UINTEGER,
+    tid UINTEGER,
+    name TEXT,
+    state TEXT,
+    utime UBIGINT,
+    stime UBIGINT,
+    processor UINTEGER,
+    lstart TIMESTAMP,
+    started_at TIMESTAMP,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
 const SILVER_OPEN_FILES: &str = r#"
 CREATE OR REPLACE TABLE silver_open_files (
     _id INTEGER PRIMARY KEY,
     command TEXT,
     pid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
     fd TEXT,
     type TEXT,
     device TEXT,
@@ -300,6 +489,95 @@ CREATE OR REPLACE TABLE silver_open_files (
     ip_source_port TEXT,
     ip_destination_address TEXT,
     ip_destination_port TEXT,
+    access TEXT,
+    lock TEXT,
+    file_offset UBIGINT,
+    protocol TEXT,
+    tcp_state TEXT,
+    recv_queue UBIGINT,
+    send_queue UBIGINT,
+    peer_node TEXT,
+    link_count UBIGINT,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
+const SILVER_PROCESS_EVENT: &str = r#"
+CREATE OR REPLACE TABLE silver_process_event (
+    _id INTEGER PRIMARY KEY,
+    event TEXT,
+    pid UINTEGER,
+    ppid UINTEGER,
+    started_at TIMESTAMP,
+    command TEXT,
+    exit_code INTEGER,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
+const SILVER_PROCESS_ENVIRON: &str = r#"
+CREATE OR REPLACE TABLE silver_process_environ (
+    _id INTEGER PRIMARY KEY,
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    key TEXT,
+    value TEXT,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
+const SILVER_PROCESS_LIBRARY: &str = r#"
+CREATE OR REPLACE TABLE silver_process_library (
+    _id INTEGER PRIMARY KEY,
+    pid UINTEGER,
+    command TEXT,
+    path TEXT,
+    device TEXT,
+    inode UBIGINT,
+    deleted BOOLEAN,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
+const SILVER_PROCESS_EXECUTABLE: &str = r#"
+CREATE OR REPLACE TABLE silver_process_executable (
+    _id INTEGER PRIMARY KEY,
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    path TEXT,
+    device UBIGINT,
+    inode UBIGINT,
+    size UBIGINT,
+    modified_at TIMESTAMP,
+    sha256 TEXT,
+    deleted BOOLEAN,
+    replaced BOOLEAN,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
+    inserted_at TIMESTAMP,
+    svr_ingestion_duration INTERVAL
+);
+"#;
+
+const SILVER_FILE_ACTIVITY: &str = r#"
+CREATE OR REPLACE TABLE silver_file_activity (
+    _id INTEGER PRIMARY KEY,
+    event TEXT,
+    path TEXT,
+    directory BOOLEAN,
+    cookie UINTEGER,
+    created_at TIMESTAMP,
+    brz_ingestion_duration INTERVAL,
     inserted_at TIMESTAMP,
     svr_ingestion_duration INTERVAL
 );
@@ -310,7 +588,7 @@ CREATE OR REPLACE TABLE silver_network_packet (
     _id UHUGEINT PRIMARY KEY,
     interface TEXT,
     length UINTEGER,
-    created_at TIMESTAMP,
+    created_at TIMESTAMP_NS,
     brz_ingestion_duration INTERVAL,
     data_link TEXT,
     network TEXT,
@@ -465,7 +743,7 @@ CREATE OR REPLACE TABLE gold_file_host (
 const GOLD_FILE_USER: &str = r#"
 CREATE OR REPLACE TABLE gold_file_user (
     name TEXT,
-    uid INTEGER,
+    uid UINTEGER,
     inserted_at TIMESTAMP,
     PRIMARY KEY (name, uid)
 );
@@ -477,10 +755,110 @@ const GOLD_DIM_PROCESS: &str = r#"
 CREATE OR REPLACE TABLE gold_dim_process (
 	pid UINTEGER,
 	ppid UINTEGER,
-	uid INTEGER,
+	uid UINTEGER,
+	euid UINTEGER,
+	gid UINTEGER,
+	egid UINTEGER,
+	user_name TEXT,
 	command TEXT,
 	full_command TEXT,
+	exe TEXT,
+	tty TEXT,
+	started_at TIMESTAMP,
+	exited_at TIMESTAMP,
+	exit_code INTEGER,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at)
+);
+"#;
+
+const GOLD_DIM_PROCESS_CONTAINER: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_process_container (
+	pid UINTEGER,
+	started_at TIMESTAMP,
+	container_id TEXT,
+	pod_uid TEXT,
+	container_runtime TEXT,
+	systemd_unit TEXT,
+	systemd_slice TEXT,
+	cgroup TEXT,
+	mnt_ns UBIGINT,
+	net_ns UBIGINT,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at)
+);
+"#;
+
+const GOLD_DIM_PROCESS_TREE: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_process_tree (
+	pid UINTEGER,
+	started_at TIMESTAMP,
+	ppid UINTEGER,
+	parent_started_at TIMESTAMP,
+	depth UINTEGER,
+	root_pid UINTEGER,
+	root_started_at TIMESTAMP,
+	ancestor_path TEXT,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at)
+);
+"#;
+
+const GOLD_DIM_PROCESS_SECURITY: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_process_security (
+	pid UINTEGER,
+	started_at TIMESTAMP,
+	cap_effective UBIGINT,
+	cap_permitted UBIGINT,
+	cap_bounding UBIGINT,
+	capabilities TEXT,
+	no_new_privs BOOLEAN,
+	seccomp TEXT,
+	lsm_label TEXT,
+	privileged BOOLEAN,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at)
+);
+"#;
+
+const GOLD_DIM_PROCESS_ENVIRON: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_process_environ (
+	pid UINTEGER,
+	started_at TIMESTAMP,
+	key TEXT,
+	value TEXT,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at, key)
+);
+"#;
+
+const GOLD_DIM_PROCESS_LIBRARY: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_process_library (
+	pid UINTEGER,
+	started_at TIMESTAMP,
+	command TEXT,
+	path TEXT,
+	device TEXT,
+	inode UBIGINT,
+	deleted BOOLEAN,
+	first_seen_at TIMESTAMP,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, started_at, path, inode)
+);
+"#;
+
+const GOLD_DIM_EXECUTABLE: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_executable (
+	pid UINTEGER,
 	started_at TIMESTAMP,
+	path TEXT,
+	device UBIGINT,
+	inode UBIGINT,
+	size UBIGINT,
+	modified_at TIMESTAMP,
+	sha256 TEXT,
+	deleted BOOLEAN,
+	replaced BOOLEAN,
 	inserted_at TIMESTAMP,
 	PRIMARY KEY (pid, started_at)
 );
@@ -489,7 +867,7 @@ CREATE OR REPLACE TABLE gold_dim_process (
 const GOLD_DIM_FILE_REG: &str = r#"
 CREATE OR REPLACE TABLE gold_dim_file_reg (
 	pid UINTEGER,
-	uid INTEGER,
+	uid UINTEGER,
 	fd TEXT,
 	node TEXT,
 	command TEXT,
@@ -500,6 +878,23 @@ CREATE OR REPLACE TABLE gold_dim_file_reg (
 );
 "#;
 
+const GOLD_DIM_FILE_DELETED_OPEN: &str = r#"
+CREATE OR REPLACE TABLE gold_dim_file_deleted_open (
+	pid UINTEGER,
+	uid UINTEGER,
+	fd TEXT,
+	device TEXT,
+	node TEXT,
+	command TEXT,
+	name TEXT,
+	reclaimable_size UBIGINT,
+	started_at TIMESTAMP,
+	last_seen_at TIMESTAMP,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (pid, fd, node)
+);
+"#;
+
 const GOLD_DIM_NETWORK_INTERFACE: &str = r#"
 CREATE OR REPLACE TABLE gold_dim_network_interface (
     _id UBIGINT PRIMARY KEY,
@@ -517,7 +912,7 @@ const GOLD_DIM_NETWORK_SOCKET: &str = r#"
 CREATE OR REPLACE TABLE gold_dim_network_socket (
     _id UBIGINT PRIMARY KEY,
     pid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
     command TEXT,
     source_address INET,
     source_port USMALLINT,
@@ -531,7 +926,7 @@ CREATE OR REPLACE TABLE gold_dim_network_socket (
 const GOLD_DIM_NETWORK_OPEN_PORT: &str = r#"
 CREATE OR REPLACE TABLE gold_dim_network_open_port (
     pid UINTEGER,
-    uid INTEGER,
+    uid UINTEGER,
     command TEXT,
     port USMALLINT,
     started_at TIMESTAMP,
@@ -568,11 +963,39 @@ CREATE OR REPLACE TABLE gold_fact_process (
     created_at TIMESTAMP,
     pcpu FLOAT,
     pmem FLOAT,
+    utime UBIGINT,
+    stime UBIGINT,
+    rss UBIGINT,
+    vsz UBIGINT,
+    threads UINTEGER,
+    nice INTEGER,
+    priority INTEGER,
+    read_bytes UBIGINT,
+    write_bytes UBIGINT,
+    pcpu_interval FLOAT,
     inserted_at TIMESTAMP,
     PRIMARY KEY (pid, started_at, created_at)
 );
 "#;
 
+const GOLD_FACT_THREAD: &str = r#"
+CREATE OR REPLACE TABLE gold_fact_thread (
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    tid UINTEGER,
+    created_at TIMESTAMP,
+    name TEXT,
+    state TEXT,
+    utime UBIGINT,
+    stime UBIGINT,
+    processor UINTEGER,
+    thread_started_at TIMESTAMP,
+    pcpu_interval FLOAT,
+    inserted_at TIMESTAMP,
+    PRIMARY KEY (pid, started_at, tid, created_at)
+);
+"#;
+
 const GOLD_FACT_FILE_REG: &str = r#"
 CREATE OR REPLACE TABLE gold_fact_file_reg (
     pid UINTEGER,
@@ -585,12 +1008,39 @@ CREATE OR REPLACE TABLE gold_fact_file_reg (
 );
 "#;
 
+const GOLD_FACT_FILE_ACTIVITY: &str = r#"
+CREATE OR REPLACE TABLE gold_fact_file_activity (
+    event TEXT,
+    path TEXT,
+    directory BOOLEAN,
+    cookie UINTEGER,
+    pid UINTEGER,
+    command TEXT,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    PRIMARY KEY (path, event, created_at)
+);
+"#;
+
+const GOLD_FACT_NETWORK_SOCKET: &str = r#"
+CREATE OR REPLACE TABLE gold_fact_network_socket (
+    socket_id UBIGINT,
+    pid UINTEGER,
+    state TEXT,
+    recv_queue UBIGINT,
+    send_queue UBIGINT,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    PRIMARY KEY (socket_id, created_at)
+);
+"#;
+
 const GOLD_FACT_NETWORK_PACKET: &str = r#"
 CREATE OR REPLACE TABLE gold_fact_network_packet (
     _id UHUGEINT PRIMARY KEY,
     interface TEXT,
     length UINTEGER,
-    created_at TIMESTAMP,
+    created_at TIMESTAMP_NS,
     data_link TEXT,
     network TEXT,
     transport TEXT,
@@ -608,7 +1058,7 @@ CREATE OR REPLACE TABLE gold_fact_network_ip (
     source_port USMALLINT,
     destination_address INET,
     destination_port USMALLINT,
-    created_at TIMESTAMP,
+    created_at TIMESTAMP_NS,
     inserted_at TIMESTAMP
 );
 "#;
@@ -623,6 +1073,38 @@ CREATE OR REPLACE TABLE gold_fact_process_network (
 );
 "#;
 
+const GOLD_FACT_PROCESS_IPC: &str = r#"
+CREATE OR REPLACE TABLE gold_fact_process_ipc (
+    pid UINTEGER,
+    fd TEXT,
+    peer_pid UINTEGER,
+    peer_fd TEXT,
+    type TEXT,
+    node TEXT,
+    name TEXT,
+    created_at TIMESTAMP,
+    inserted_at TIMESTAMP,
+    PRIMARY KEY (pid, fd, peer_pid, peer_fd, created_at)
+);
+"#;
+
+const GOLD_FACT_PROCESS_EVENT: &str = r#"
+CREATE OR REPLACE TABLE gold_fact_process_event (
+    pid UINTEGER,
+    started_at TIMESTAMP,
+    event TEXT,
+    event_at TIMESTAMP,
+    last_seen_at TIMESTAMP,
+    ppid UINTEGER,
+    command TEXT,
+    full_command TEXT,
+    lifetime INTERVAL,
+    pid_reused BOOLEAN,
+    inserted_at TIMESTAMP,
+    PRIMARY KEY (pid, started_at, event)
+);
+"#;
+
 // GOLD TECHNICAL
 
 const GOLD_TECH_TABLE_COUNT: &str = r#"
@@ -648,12 +1130,107 @@ CREATE OR REPLACE TABLE gold_tech_chrono (
 );
 "#;
 
+const GOLD_TECH_CAPTURE_STATS: &str = r#"
+CREATE OR REPLACE TABLE gold_tech_capture_stats (
+	interface TEXT,
+	received UBIGINT,
+	kernel_dropped UBIGINT,
+	interface_dropped UBIGINT,
+	data_link_failures UBIGINT,
+	network_failures UBIGINT,
+	transport_failures UBIGINT,
+	application_failures UBIGINT,
+	channel_dropped UBIGINT,
+	inserted_at TIMESTAMP,
+	PRIMARY KEY (interface, inserted_at)
+);
+"#;
+
+// GOLD VIEW
+
+const GOLD_VIEW_PRIVILEGED_PROCESS: &str = r#"
+CREATE OR REPLACE VIEW gold_view_privileged_process AS
+SELECT
+	pro.pid,
+	pro.started_at,
+	pro.uid,
+	pro.euid,
+	pro.user_name,
+	pro.command,
+	pro.full_command,
+	pro.exe,
+	sec.capabilities,
+	sec.cap_effective,
+	sec.no_new_privs,
+	sec.seccomp,
+	sec.lsm_label,
+	por.port
+FROM gold_dim_process_security sec
+JOIN
+(
+	SELECT *, LEAD(started_at) OVER (PARTITION BY pid ORDER BY started_at) AS next_started_at
+	FROM gold_dim_process
+) pro ON sec.pid = pro.pid AND sec.started_at = pro.started_at
+LEFT JOIN gold_dim_network_open_port por ON sec.pid = por.pid
+	AND por.started_at >= pro.started_at
+	AND (pro.next_started_at IS NULL OR por.started_at < pro.next_started_at)
+WHERE sec.privileged;
+"#;
+
+const GOLD_VIEW_NETWORK_SOCKET: &str = r#"
+CREATE OR REPLACE VIEW gold_view_network_socket AS
+SELECT
+	HASH(pid, source_address, source_port, destination_address, destination_port) AS socket_id,
+	*
+FROM
+(
+	SELECT
+		ofn.pid,
+		ofn.uid,
+		ofn.command,
+		ofn.fd,
+		CASE
+			WHEN ofn.ip_source_address = '*' THEN NULL
+			ELSE REPLACE(COALESCE(hos1.address, ofn.ip_source_address), '-', ':')
+		END AS source_address,
+		CASE
+			WHEN ofn.ip_source_port IN ('*', '') THEN NULL
+			ELSE COALESCE(ser1.port::TEXT, ofn.ip_source_port)
+		END AS source_port,
+		CASE
+			WHEN ofn.ip_destination_address IN ('*', '') THEN NULL
+			ELSE COALESCE(hos2.address, ofn.ip_destination_address)
+		END AS destination_address,
+		CASE
+			WHEN ofn.ip_destination_port IN ('*', '') THEN NULL
+			ELSE COALESCE(ser2.port::TEXT, ofn.ip_destination_port)
+		END AS destination_port,
+		ofn.tcp_state,
+		ofn.recv_queue,
+		ofn.send_queue,
+		ofn.created_at
+	FROM silver_open_files ofn
+	LEFT JOIN gold_file_host hos1 ON LOWER(ofn.ip_source_address) = LOWER(hos1.name)
+	LEFT JOIN gold_file_host hos2 ON LOWER(ofn.ip_destination_address) = LOWER(hos2.name)
+	LEFT JOIN gold_file_service ser1 ON LOWER(ofn.ip_source_port) = LOWER(ser1.name)
+	LEFT JOIN gold_file_service ser2 ON LOWER(ofn.ip_destination_port) = LOWER(ser2.name)
+	WHERE UPPER(ofn.type) IN ('IPV4', 'IPV6')
+);
+"#;
+
 pub fn create_schema_request() -> String {
     format!(
         r#"{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}
-           {} {} {} {} {} {} {} {} {} {} {} {} {}"#,
+           {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}
+           {} {} {} {} {} {} {} {} {} {} {} {}"#,
         BRONZE_PROCESS_LIST,
+        BRONZE_THREAD_LIST,
         BRONZE_OPEN_FILES,
+        BRONZE_PROCESS_EVENT,
+        BRONZE_PROCESS_ENVIRON,
+        BRONZE_PROCESS_LIBRARY,
+        BRONZE_PROCESS_EXECUTABLE,
+        BRONZE_FILE_ACTIVITY,
         BRONZE_NETWORK_PACKET,
         BRONZE_NETWORK_INTERFACE,
         BRONZE_NETWORK_ETHERNET,
@@ -669,7 +1246,13 @@ pub fn create_schema_request() -> String {
         BRONZE_NETWORK_DNS_RECORD,
         BRONZE_NETWORK_HTTP,
         SILVER_PROCESS_LIST,
+        SILVER_THREAD_LIST,
         SILVER_OPEN_FILES,
+        SILVER_PROCESS_EVENT,
+        SILVER_PROCESS_ENVIRON,
+        SILVER_PROCESS_LIBRARY,
+        SILVER_PROCESS_EXECUTABLE,
+        SILVER_FILE_ACTIVITY,
         SILVER_NETWORK_PACKET,
         SILVER_NETWORK_INTERFACE,
         SILVER_NETWORK_ETHERNET,
@@ -681,19 +1264,34 @@ pub fn create_schema_request() -> String {
         GOLD_FILE_HOST,
         GOLD_FILE_USER,
         GOLD_DIM_PROCESS,
+        GOLD_DIM_PROCESS_CONTAINER,
+        GOLD_DIM_PROCESS_TREE,
+        GOLD_DIM_PROCESS_SECURITY,
+        GOLD_DIM_PROCESS_ENVIRON,
+        GOLD_DIM_PROCESS_LIBRARY,
+        GOLD_DIM_EXECUTABLE,
         GOLD_DIM_FILE_REG,
+        GOLD_DIM_FILE_DELETED_OPEN,
         GOLD_DIM_NETWORK_INTERFACE,
         GOLD_DIM_NETWORK_SOCKET,
         GOLD_DIM_NETWORK_OPEN_PORT,
         GOLD_DIM_NETWORK_FOREIGN_IP,
         GOLD_DIM_NETWORK_HOST,
         GOLD_FACT_PROCESS,
+        GOLD_FACT_THREAD,
         GOLD_FACT_FILE_REG,
+        GOLD_FACT_FILE_ACTIVITY,
+        GOLD_FACT_NETWORK_SOCKET,
         GOLD_FACT_NETWORK_PACKET,
         GOLD_FACT_NETWORK_IP,
         GOLD_FACT_PROCESS_NETWORK,
+        GOLD_FACT_PROCESS_IPC,
+        GOLD_FACT_PROCESS_EVENT,
         GOLD_TECH_TABLE_COUNT,
-        GOLD_TECH_CHRONO
+        GOLD_TECH_CHRONO,
+        GOLD_TECH_CAPTURE_STATS,
+        GOLD_VIEW_PRIVILEGED_PROCESS,
+        GOLD_VIEW_NETWORK_SOCKET
     )
 }
 
@@ -724,7 +1322,40 @@ pub mod tests {
 
         if let Some(row) = rows.next().unwrap() {
             let count: usize = row.get(0).unwrap();
-            assert_eq!(count, 42);
+            assert_eq!(count, 69);
+        }
+    }
+
+    #[test]
+    fn test_privileged_process_reused_pid() {
+        let connection = create_test_connection();
+        connection
+            .execute_batch(
+                r#"
+                INSERT INTO gold_dim_process (pid, command, started_at) VALUES
+                    (4242, 'sshd', '2024-09-20 10:00:00'),
+                    (4242, 'nc', '2024-09-20 11:00:00');
+                INSERT INTO gold_dim_process_security (pid, started_at, privileged) VALUES
+                    (4242, '2024-09-20 10:00:00', true),
+                    (4242, '2024-09-20 11:00:00', false);
+                INSERT INTO gold_dim_network_open_port (pid, port, started_at) VALUES
+                    (4242, 22, '2024-09-20 10:00:05'),
+                    (4242, 4444, '2024-09-20 11:00:05');
+                "#,
+            )
+            .unwrap();
+        let mut statement = connection
+            .prepare("SELECT count(*), max(command), max(port) FROM gold_view_privileged_process;")
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let command: String = row.get(1).unwrap();
+            let port: u16 = row.get(2).unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(command, "sshd");
+            assert_eq!(port, 22);
         }
     }
 }
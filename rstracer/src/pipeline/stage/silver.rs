@@ -6,9 +6,38 @@ SELECT
     pid,
     ppid,
     uid,
+    euid,
+    gid,
+    egid,
     lstart,
     pcpu,
     pmem,
+    utime,
+    stime,
+    rss,
+    vsz,
+    threads,
+    nice,
+    priority,
+    tty,
+    exe,
+    read_bytes,
+    write_bytes,
+    cgroup,
+    container_id,
+    pod_uid,
+    container_runtime,
+    systemd_unit,
+    systemd_slice,
+    mnt_ns,
+    net_ns,
+    cap_effective,
+    cap_permitted,
+    cap_bounding,
+    capabilities,
+    no_new_privs,
+    seccomp,
+    lsm_label,
     status,
     command,
     created_at,
@@ -20,6 +49,28 @@ FROM bronze_process_list
 );
 "#;
 
+const SILVER_THREAD_LIST: &str = r#"
+INSERT OR IGNORE INTO silver_thread_list BY NAME
+(
+SELECT
+    _id,
+    pid,
+    tid,
+    name,
+    state,
+    utime,
+    stime,
+    processor,
+    lstart,
+    started_at,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_thread_list
+);
+"#;
+
 const SILVER_OPEN_FILES: &str = r#"
 INSERT OR IGNORE INTO silver_open_files BY NAME
 (
@@ -36,27 +87,117 @@ SELECT
     name,
     created_at,
     brz_ingestion_duration,
-    CASE
-        WHEN REGEXP_MATCHES(ip_source_address, '[:\-]') THEN REGEXP_REPLACE(SPLIT_PART(ip_source_address, '.', 1), '[\[\]]', '', 'g')
-        ELSE ip_source_address
-    END AS ip_source_address,
-    ip_source_port,
-    REGEXP_REPLACE(ip_destination_address, '[\[\]]', '', 'g') AS ip_destination_address,
-    ip_destination_port,
-    inserted_at,
-    svr_ingestion_duration
-FROM
-    (
-    SELECT
-        *,
-        REGEXP_EXTRACT(SPLIT_PART(name, '->', 1), '^([a-zA-Z0-9\-\.\*\:\[\]]+):([a-zA-Z0-9\-\.\*\:\[\]]+)$', 1) AS ip_source_address,
-        REGEXP_EXTRACT(SPLIT_PART(name, '->', 1), '^([a-zA-Z0-9\-\.\*\:\[\]]+):([a-zA-Z0-9\-\.\*\:\[\]]+)$', 2) AS ip_source_port,
-        REGEXP_EXTRACT(SPLIT_PART(name, '->', 2), '^([a-zA-Z0-9\-\.\*\:\[\]]+):([a-zA-Z0-9\-\.\*\:\[\]]+)$', 1) AS ip_destination_address,
-        REGEXP_EXTRACT(SPLIT_PART(name, '->', 2), '^([a-zA-Z0-9\-\.\*\:\[\]]+):([a-zA-Z0-9\-\.\*\:\[\]]+)$', 2) AS ip_destination_port,
-        CURRENT_TIMESTAMP AS inserted_at,
-        AGE(inserted_at) AS svr_ingestion_duration
-    FROM bronze_open_files
-    )
+    COALESCE(ip_source_address, '') AS ip_source_address,
+    COALESCE(ip_source_port, '') AS ip_source_port,
+    COALESCE(ip_destination_address, '') AS ip_destination_address,
+    COALESCE(ip_destination_port, '') AS ip_destination_port,
+    access,
+    lock,
+    file_offset,
+    protocol,
+    tcp_state,
+    recv_queue,
+    send_queue,
+    peer_node,
+    link_count,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_open_files
+);
+"#;
+
+const SILVER_PROCESS_EVENT: &str = r#"
+INSERT OR IGNORE INTO silver_process_event BY NAME
+(
+SELECT
+    _id,
+    event,
+    pid,
+    ppid,
+    started_at,
+    command,
+    exit_code,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_process_event
+);
+"#;
+
+const SILVER_PROCESS_ENVIRON: &str = r#"
+INSERT OR IGNORE INTO silver_process_environ BY NAME
+(
+SELECT
+    _id,
+    pid,
+    started_at,
+    key,
+    value,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_process_environ
+);
+"#;
+
+const SILVER_PROCESS_LIBRARY: &str = r#"
+INSERT OR IGNORE INTO silver_process_library BY NAME
+(
+SELECT
+    _id,
+    pid,
+    command,
+    path,
+    device,
+    inode,
+    deleted,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_process_library
+);
+"#;
+
+const SILVER_PROCESS_EXECUTABLE: &str = r#"
+INSERT OR IGNORE INTO silver_process_executable BY NAME
+(
+SELECT
+    _id,
+    pid,
+    started_at,
+    path,
+    device,
+    inode,
+    size,
+    modified_at,
+    sha256,
+    deleted,
+    replaced,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_process_executable
+);
+"#;
+
+const SILVER_FILE_ACTIVITY: &str = r#"
+INSERT OR IGNORE INTO silver_file_activity BY NAME
+(
+SELECT
+    _id,
+    event,
+    path,
+    directory,
+    cookie,
+    created_at,
+    brz_ingestion_duration,
+    CURRENT_TIMESTAMP AS inserted_at,
+    AGE(inserted_at) AS svr_ingestion_duration
+FROM bronze_file_activity
 );
 "#;
 
@@ -337,9 +478,15 @@ FROM bronze_network_arp arp LEFT JOIN bronze_network_packet packet ON arp.packet
 
 pub fn request() -> String {
     format!(
-        "{} {} {} {} {} {} {} {} {}",
+        "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
         SILVER_PROCESS_LIST,
+        SILVER_THREAD_LIST,
         SILVER_OPEN_FILES,
+        SILVER_PROCESS_EVENT,
+        SILVER_PROCESS_ENVIRON,
+        SILVER_PROCESS_LIBRARY,
+        SILVER_PROCESS_EXECUTABLE,
+        SILVER_FILE_ACTIVITY,
         SILVER_NETWORK_PACKET,
         SILVER_NETWORK_INTERFACE,
         SILVER_NETWORK_ETHERNET,
@@ -0,0 +1,76 @@
+use network::stats::CaptureStats;
+
+// Counters are cumulative since startup, every snapshot is kept as its own row.
+pub fn capture_stats_request(stats: &[CaptureStats]) -> String {
+    let values: Vec<String> = stats
+        .iter()
+        .map(|stats| {
+            format!(
+                "('{}', {}, {}, {}, {}, {}, {}, {}, {}, CURRENT_TIMESTAMP)",
+                stats.interface.replace('\'', "''"),
+                stats.received,
+                stats.kernel_dropped,
+                stats.interface_dropped,
+                stats.data_link_failures,
+                stats.network_failures,
+                stats.transport_failures,
+                stats.application_failures,
+                stats.channel_dropped
+            )
+        })
+        .collect();
+
+    if values.is_empty() {
+        "".to_string()
+    } else {
+        format!(
+            "INSERT INTO gold_tech_capture_stats (interface, received, kernel_dropped, interface_dropped, data_link_failures, network_failures, transport_failures, application_failures, channel_dropped, inserted_at) VALUES {};",
+            values.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::stage::tests::create_test_connection;
+    use network::capture::Layer;
+    use network::stats::StatsRegistry;
+
+    #[test]
+    fn test_capture_stats_request() {
+        let connection = create_test_connection();
+        let registry = StatsRegistry::default();
+        registry.counters("eth0").add_channel_dropped(256);
+        registry.counters("lo").add_parse_failure(&Layer::Transport);
+        connection
+            .execute_batch(&capture_stats_request(&registry.snapshot()))
+            .unwrap();
+        registry.counters("eth0").add_channel_dropped(256);
+        connection
+            .execute_batch(&capture_stats_request(&registry.snapshot()))
+            .unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT count(*), count(DISTINCT interface), max(channel_dropped), max(transport_failures) FROM gold_tech_capture_stats;",
+            )
+            .unwrap();
+        let mut rows = statement.query([]).unwrap();
+
+        if let Some(row) = rows.next().unwrap() {
+            let count: usize = row.get(0).unwrap();
+            let interfaces: usize = row.get(1).unwrap();
+            let channel_dropped: u64 = row.get(2).unwrap();
+            let transport_failures: u64 = row.get(3).unwrap();
+            assert_eq!(count, 4);
+            assert_eq!(interfaces, 2);
+            assert_eq!(channel_dropped, 512);
+            assert_eq!(transport_failures, 1);
+        }
+    }
+
+    #[test]
+    fn test_capture_stats_request_empty() {
+        assert_eq!(capture_stats_request(&[]), "");
+    }
+}
@@ -9,7 +9,7 @@ pub fn request(config: &VacuumConfig) -> String {
             if table.starts_with(&layer.0)
                 && layer.1 > 0
                 && !table.contains("gold_file_")
-                && !table.contains("_tech_")
+                && (!table.contains("_tech_") || table == "gold_tech_capture_stats")
             {
                 query.push_str(&format!(
                     "DELETE FROM {} WHERE inserted_at + '{} seconds' < CURRENT_TIMESTAMP;",
@@ -35,8 +35,9 @@ mod tests {
         let request = request(&vacuum_config);
 
         assert!(!request.contains("gold_file_"));
-        assert!(!request.contains("_tech_"));
-        assert_eq!(request.matches("DELETE FROM").count(), 37);
+        assert!(!request.contains("gold_tech_table_count"));
+        assert!(!request.contains("gold_tech_chrono"));
+        assert_eq!(request.matches("DELETE FROM").count(), 62);
         assert!(request.contains(
             "DELETE FROM bronze_process_list WHERE inserted_at + '15 seconds' < CURRENT_TIMESTAMP"
         ));
@@ -46,6 +47,9 @@ mod tests {
         assert!(request.contains(
             "DELETE FROM gold_fact_process WHERE inserted_at + '1000 seconds' < CURRENT_TIMESTAMP"
         ));
+        assert!(request.contains(
+            "DELETE FROM gold_tech_capture_stats WHERE inserted_at + '1000 seconds' < CURRENT_TIMESTAMP"
+        ));
     }
 
     #[test]
@@ -57,7 +61,7 @@ mod tests {
         };
         let request = request(&vacuum_config);
         assert!(!request.contains("gold_"));
-        assert_eq!(request.matches("DELETE FROM").count(), 25);
+        assert_eq!(request.matches("DELETE FROM").count(), 37);
         assert!(request.contains(
             "DELETE FROM bronze_process_list WHERE inserted_at + '15 seconds' < CURRENT_TIMESTAMP"
         ));